@@ -0,0 +1,204 @@
+use std::{error::Error, fmt, io, path::PathBuf, time::Duration};
+
+use crate::exit;
+
+/// Where in the input a parse error was found, 1-based.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Location {
+    pub line: usize,
+    pub column: Option<usize>,
+}
+
+impl Location {
+    pub fn line(line: usize) -> Self {
+        Location { line, column: None }
+    }
+
+    // the position of `rest` in `input`, where `rest` is a suffix of `input` left by a parser
+    fn of_remainder(input: &str, rest: &str) -> Option<Self> {
+        let offset = input.len().checked_sub(rest.len())?;
+        if !std::ptr::eq(input[offset..].as_ptr(), rest.as_ptr()) {
+            return None;
+        }
+        let consumed = &input[..offset];
+        let line = consumed.matches('\n').count() + 1;
+        let column = consumed.rsplit('\n').next().unwrap_or_default().chars().count() + 1;
+        Some(Location { line, column: Some(column) })
+    }
+}
+
+impl fmt::Display for Location {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.column {
+            Some(column) => write!(f, "line {}, column {column}", self.line),
+            None => write!(f, "line {}", self.line),
+        }
+    }
+}
+
+/// Everything that can go wrong while solving a day or running an xtask.
+#[derive(Debug)]
+pub enum AocError {
+    Io(io::Error),
+    Parse {
+        location: Option<Location>,
+        msg: String,
+    },
+    InputNotFound(PathBuf),
+    /// The input parsed but has no answer, like a field without a start tile
+    Unsolvable(String),
+    Timeout(Duration),
+    /// The solver panicked, with the panic message when it had one
+    Panicked(String),
+    /// An answer that differs from the one recorded for it
+    Mismatch {
+        part: String,
+        expected: String,
+        actual: String,
+    },
+    /// An xtask that cannot be done, like scaffolding a day that already exists
+    Task(String),
+}
+
+impl AocError {
+    pub fn parse(msg: impl Into<String>) -> Self {
+        AocError::Parse { location: None, msg: msg.into() }
+    }
+
+    pub fn parse_at(location: Location, msg: impl Into<String>) -> Self {
+        AocError::Parse { location: Some(location), msg: msg.into() }
+    }
+
+    /// A nom error located in the `input` it was parsing.
+    pub fn parse_in(input: &str, e: nom::Err<nom::error::Error<&str>>) -> Self {
+        let location = match &e {
+            nom::Err::Error(inner) | nom::Err::Failure(inner) => {
+                Location::of_remainder(input, inner.input)
+            }
+            nom::Err::Incomplete(_) => None,
+        };
+        AocError::Parse { location, msg: e.to_string() }
+    }
+
+    pub fn unsolvable(msg: impl Into<String>) -> Self {
+        AocError::Unsolvable(msg.into())
+    }
+
+    /// The line of a parse error, if it has one.
+    pub fn line(&self) -> Option<usize> {
+        match self {
+            AocError::Parse { location, .. } => location.map(|l| l.line),
+            _ => None,
+        }
+    }
+
+    /// The process exit code for scripts, see [`exit`].
+    pub fn exit_code(&self) -> u8 {
+        match self {
+            AocError::Io(_) => exit::IO,
+            AocError::Parse { .. } => exit::PARSE,
+            AocError::InputNotFound(_) => exit::INPUT_NOT_FOUND,
+            AocError::Unsolvable(_) => exit::UNSOLVABLE,
+            AocError::Timeout(_) => exit::TIMEOUT,
+            AocError::Panicked(_) => exit::PANIC,
+            AocError::Mismatch { .. } => exit::MISMATCH,
+            AocError::Task(_) => exit::TASK,
+        }
+    }
+}
+
+impl fmt::Display for AocError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AocError::Io(e) => write!(f, "io error: {e}"),
+            AocError::Parse { location: Some(location), msg } => {
+                write!(f, "parse error at {location}: {msg}")
+            }
+            AocError::Parse { location: None, msg } => write!(f, "parse error: {msg}"),
+            AocError::InputNotFound(path) => write!(f, "input {} not found", path.display()),
+            AocError::Unsolvable(msg) => write!(f, "unsolvable: {msg}"),
+            AocError::Timeout(limit) => write!(f, "timed out after {limit:?}"),
+            AocError::Panicked(msg) => write!(f, "panicked: {msg}"),
+            AocError::Mismatch { part, expected, actual } => {
+                write!(f, "{part}: expected {expected}, got {actual}")
+            }
+            AocError::Task(msg) => f.write_str(msg),
+        }
+    }
+}
+
+impl Error for AocError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            AocError::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for AocError {
+    fn from(e: io::Error) -> Self {
+        AocError::Io(e)
+    }
+}
+
+impl From<String> for AocError {
+    fn from(msg: String) -> Self {
+        AocError::Task(msg)
+    }
+}
+
+impl From<&str> for AocError {
+    fn from(msg: &str) -> Self {
+        AocError::Task(msg.to_owned())
+    }
+}
+
+/// Without the whole input only the distance from its end is known, use
+/// [`AocError::parse_in`] for a line and column.
+impl From<nom::Err<nom::error::Error<&str>>> for AocError {
+    fn from(e: nom::Err<nom::error::Error<&str>>) -> Self {
+        match &e {
+            nom::Err::Error(inner) | nom::Err::Failure(inner) => {
+                AocError::parse(format!("{e} ({} bytes before the end)", inner.input.len()))
+            }
+            nom::Err::Incomplete(_) => AocError::parse(e.to_string()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nom::character::complete::u32;
+
+    #[test]
+    fn test_parse_in() {
+        let input = "12\n3x";
+        let rest = &input[4..];
+        let e = u32::<&str, nom::error::Error<&str>>(rest).unwrap_err();
+        let error = AocError::parse_in(input, e);
+        assert_eq!(error.line(), Some(2));
+        assert!(error.to_string().starts_with("parse error at line 2, column 2: "));
+        assert_eq!(error.exit_code(), 4);
+
+        let e = u32::<&str, nom::error::Error<&str>>(rest).unwrap_err();
+        assert!(AocError::from(e).to_string().ends_with("(1 bytes before the end)"));
+    }
+
+    #[test]
+    fn test_exit_codes_are_distinct() {
+        let errors = [
+            AocError::Io(io::Error::other("disk")),
+            AocError::parse("bad"),
+            AocError::InputNotFound(PathBuf::from("input/day1.txt")),
+            AocError::unsolvable("no start"),
+            AocError::Timeout(Duration::from_secs(1)),
+            AocError::Panicked("index out of bounds".into()),
+            AocError::Mismatch { part: "part1".into(), expected: "1".into(), actual: "2".into() },
+            AocError::from("day5 already exists"),
+        ];
+        let codes: Vec<u8> = errors.iter().map(AocError::exit_code).collect();
+        assert_eq!(codes, [3, 4, 5, 6, 7, 9, 8, 10]);
+    }
+}
@@ -0,0 +1,11 @@
+//! Process exit codes of the `aoc` runner and `xtask`, one per kind of failure; clap already uses
+//! 1 and 2.
+
+pub const IO: u8 = 3;
+pub const PARSE: u8 = 4;
+pub const INPUT_NOT_FOUND: u8 = 5;
+pub const UNSOLVABLE: u8 = 6;
+pub const TIMEOUT: u8 = 7;
+pub const MISMATCH: u8 = 8;
+pub const PANIC: u8 = 9;
+pub const TASK: u8 = 10;
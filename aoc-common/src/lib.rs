@@ -0,0 +1,7 @@
+//! The few pieces `aoc` and `xtask` both need, kept apart so the xtask repair tools still
+//! build while `aoc` does not.
+
+pub mod diff;
+pub mod error;
+pub mod exit;
+pub mod text;
@@ -0,0 +1,25 @@
+//! Puzzle text as the days see it, whatever editor or browser it came from.
+
+use std::borrow::Cow;
+
+/// Drop a UTF-8 BOM, turn CRLF into LF and strip trailing whitespace from every line.
+pub fn normalize(input: &str) -> Cow<'_, str> {
+    let input = input.strip_prefix('\u{feff}').unwrap_or(input);
+    let clean = input.split('\n').all(|line| line.trim_end().len() == line.len());
+    if clean {
+        return Cow::Borrowed(input);
+    }
+    let lines: Vec<&str> = input.split('\n').map(str::trim_end).collect();
+    Cow::Owned(lines.join("\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize() {
+        assert!(matches!(normalize("a\nb\n"), Cow::Borrowed("a\nb\n")));
+        assert_eq!(normalize("\u{feff}a \r\nb\t\r\n\r\nc"), "a\nb\n\nc");
+    }
+}
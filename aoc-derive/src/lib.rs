@@ -0,0 +1,139 @@
+//! `#[derive(FromLine)]`, a `FromStr` impl generated from a pattern like
+//! `#[line("Card {id}: {winning: [u32; ' ']} | {have: [u32; ' ']}")]`.
+
+use proc_macro::TokenStream;
+use proc_macro2::Span;
+use quote::quote;
+use syn::{parse_macro_input, Data, DeriveInput, Error, Fields, Ident, LitStr, Type};
+
+// one piece of a `#[line(...)]` pattern
+enum Segment {
+    Literal(String),
+    Field { name: Ident, list: Option<Box<(Type, char)>> },
+}
+
+// `{name}` or `{name: [Type; 'separator']}`
+fn parse_field(inner: &str, span: Span) -> Result<Segment, Error> {
+    let (name, list) = match inner.split_once(':') {
+        Some((name, list)) => (name.trim(), Some(list.trim())),
+        None => (inner.trim(), None),
+    };
+    let name = syn::parse_str::<Ident>(name)
+        .map_err(|_| Error::new(span, format!("`{name}` is not a field name")))?;
+    let Some(list) = list else {
+        return Ok(Segment::Field { name, list: None });
+    };
+
+    let invalid = || Error::new(span, format!("expected `[Type; 'separator']` for `{name}`"));
+    let list = list.strip_prefix('[').and_then(|l| l.strip_suffix(']')).ok_or_else(invalid)?;
+    let (ty, separator) = list.rsplit_once(';').ok_or_else(invalid)?;
+    let ty = syn::parse_str::<Type>(ty.trim()).map_err(|_| invalid())?;
+    let separator = syn::parse_str::<syn::LitChar>(separator.trim()).map_err(|_| invalid())?;
+    Ok(Segment::Field { name, list: Some(Box::new((ty, separator.value()))) })
+}
+
+fn parse_pattern(pattern: &LitStr) -> Result<Vec<Segment>, Error> {
+    let span = pattern.span();
+    let mut segments = vec![];
+    let mut rest = pattern.value();
+    while !rest.is_empty() {
+        match rest.find('{') {
+            Some(0) => {
+                let end = rest.find('}').ok_or_else(|| Error::new(span, "unclosed `{`"))?;
+                segments.push(parse_field(&rest[1..end], span)?);
+                rest = rest[end + 1..].to_owned();
+            }
+            Some(start) => {
+                segments.push(Segment::Literal(rest[..start].to_owned()));
+                rest = rest[start..].to_owned();
+            }
+            None => {
+                segments.push(Segment::Literal(rest));
+                rest = String::new();
+            }
+        }
+    }
+    for pair in segments.windows(2) {
+        if let [Segment::Field { name, .. }, Segment::Field { .. }] = pair {
+            return Err(Error::new(span, format!("`{name}` needs text before the next field")));
+        }
+    }
+    Ok(segments)
+}
+
+fn expand(input: DeriveInput) -> Result<proc_macro2::TokenStream, Error> {
+    let ident = &input.ident;
+    let Data::Struct(data) = &input.data else {
+        return Err(Error::new_spanned(ident, "FromLine can only be derived for structs"));
+    };
+    let Fields::Named(fields) = &data.fields else {
+        return Err(Error::new_spanned(ident, "FromLine needs a struct with named fields"));
+    };
+    let attr = input
+        .attrs
+        .iter()
+        .find(|a| a.path().is_ident("line"))
+        .ok_or_else(|| Error::new_spanned(ident, "missing #[line(\"pattern\")]"))?;
+    let pattern: LitStr = attr.parse_args()?;
+    let segments = parse_pattern(&pattern)?;
+
+    let field_names: Vec<&Ident> = fields.named.iter().filter_map(|f| f.ident.as_ref()).collect();
+    let mut seen: Vec<&Ident> = vec![];
+    for segment in &segments {
+        if let Segment::Field { name, .. } = segment {
+            if !field_names.contains(&name) {
+                let msg = format!("`{ident}` has no field `{name}`");
+                return Err(Error::new(pattern.span(), msg));
+            }
+            if seen.contains(&name) {
+                return Err(Error::new(pattern.span(), format!("`{name}` appears twice")));
+            }
+            seen.push(name);
+        }
+    }
+    if let Some(missing) = field_names.iter().find(|f| !seen.contains(f)) {
+        return Err(Error::new(pattern.span(), format!("the pattern has no `{{{missing}}}`")));
+    }
+
+    let steps = segments.iter().enumerate().map(|(i, segment)| match segment {
+        Segment::Literal(text) => quote! { cursor.literal(#text)?; },
+        Segment::Field { name, list } => {
+            let until = match segments.get(i + 1) {
+                Some(Segment::Literal(text)) => quote! { ::std::option::Option::Some(#text) },
+                _ => quote! { ::std::option::Option::None },
+            };
+            let label = name.to_string();
+            match list {
+                None => quote! { let #name = cursor.field(#label, #until)?; },
+                Some(list) => {
+                    let (ty, separator) = &**list;
+                    quote! { let #name = cursor.list::<#ty, _>(#label, #separator, #until)?; }
+                }
+            }
+        }
+    });
+
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::std::str::FromStr for #ident #ty_generics #where_clause {
+            type Err = ::aoc::utils::line::LineError;
+
+            fn from_str(line: &str) -> ::std::result::Result<Self, Self::Err> {
+                let mut cursor = ::aoc::utils::line::Cursor::new(line);
+                #(#steps)*
+                cursor.finish()?;
+                ::std::result::Result::Ok(#ident { #(#field_names),* })
+            }
+        }
+    })
+}
+
+/// Derive `FromStr` from a `#[line("...")]` pattern.
+///
+/// `{field}` parses the field with its own `FromStr`, `{field: [T; 'c']}` collects the
+/// `c` separated `T`s into it, and a space in the text matches any run of spaces.
+#[proc_macro_derive(FromLine, attributes(line))]
+pub fn derive_from_line(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(input).unwrap_or_else(Error::into_compile_error).into()
+}
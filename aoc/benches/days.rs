@@ -0,0 +1,186 @@
+use std::{fs, hint::black_box, path::Path};
+
+use aoc::commands::{day1, day10, day5};
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+
+// deterministic pseudo random numbers so generated inputs are the same on every run
+struct Lcg(u64);
+
+impl Lcg {
+    fn next(&mut self, bound: u64) -> u64 {
+        self.0 = self.0.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+        (self.0 >> 33) % bound
+    }
+}
+
+fn day1_input(lines: usize) -> String {
+    let words = ["one", "two", "three", "four", "five", "six", "seven", "eight", "nine"];
+    let mut rng = Lcg(1);
+    let mut input = String::new();
+    for _ in 0..lines {
+        input.push(char::from(b'1' + rng.next(9) as u8));
+        for _ in 0..rng.next(8) {
+            match rng.next(3) {
+                0 => input.push_str(words[rng.next(9) as usize]),
+                1 => input.push(char::from(b'1' + rng.next(9) as u8)),
+                _ => input.push(char::from(b'a' + rng.next(26) as u8)),
+            }
+        }
+        input.push('\n');
+    }
+    input
+}
+
+// a single loop around the border of a size x size field
+fn day10_input(size: usize) -> String {
+    let mut input = format!("S{}7\n", "-".repeat(size - 2));
+    for _ in 0..size - 2 {
+        input.push_str(&format!("|{}|\n", ".".repeat(size - 2)));
+    }
+    input.push_str(&format!("L{}J\n", "-".repeat(size - 2)));
+    input
+}
+
+fn day5_input(seeds: usize, entries: usize) -> String {
+    let mut rng = Lcg(5);
+    let seeds: Vec<String> =
+        (0..seeds).map(|_| format!("{} {}", rng.next(1 << 32), rng.next(1 << 24))).collect();
+    let mut input = format!("seeds: {}\n", seeds.join(" "));
+    let stages = ["seed", "soil", "fertilizer", "water", "light", "temperature", "humidity"];
+    for (i, from) in stages.iter().enumerate() {
+        let to = stages.get(i + 1).unwrap_or(&"location");
+        input.push_str(&format!("\n{from}-to-{to} map:\n"));
+        for j in 0..entries as u64 {
+            let source = j * (1 << 32) / entries as u64;
+            let length = rng.next((1 << 32) / entries as u64) + 1;
+            input.push_str(&format!("{} {source} {length}\n", rng.next((1 << 32) - length)));
+        }
+    }
+    input
+}
+
+fn bench_day1(c: &mut Criterion) {
+    let mut group = c.benchmark_group("day1");
+    let inputs = [
+        ("example", include_str!("../../examples/day1/edge_cases.txt").to_owned()),
+        ("generated", day1_input(10_000)),
+    ];
+    for (name, input) in inputs.iter() {
+        group.bench_with_input(BenchmarkId::new("extract_numbers", name), input, |b, input| {
+            b.iter(|| {
+                input.lines().filter_map(|l| day1::extract_numbers(black_box(l))).sum::<usize>()
+            })
+        });
+        group.bench_with_input(BenchmarkId::new("part2", name), input, |b, input| {
+            b.iter(|| day1::part2(black_box(input)).unwrap())
+        });
+    }
+    group.finish();
+}
+
+fn bench_day10(c: &mut Criterion) {
+    let mut group = c.benchmark_group("day10");
+    let inputs = [
+        ("example", include_str!("../../examples/day10/larger.txt").to_owned()),
+        ("generated", day10_input(100)),
+    ];
+    for (name, input) in inputs.iter() {
+        group.bench_with_input(BenchmarkId::new("parse_field", name), input, |b, input| {
+            b.iter(|| day10::parse_field(black_box(input)).unwrap())
+        });
+        let (_, field) = day10::parse_field(input).unwrap();
+        group.bench_with_input(BenchmarkId::new("find_loop", name), &field, |b, field| {
+            b.iter(|| day10::find_loop(black_box(field)).unwrap())
+        });
+        let (_, pipes) = day10::find_loop(&field).unwrap();
+        group.bench_with_input(
+            BenchmarkId::new("count_interior_positions", name),
+            &field,
+            |b, field| b.iter(|| day10::count_interior_positions(black_box(field), pipes.clone())),
+        );
+    }
+    group.finish();
+}
+
+fn bench_day5(c: &mut Criterion) {
+    use aoc::utils::cache;
+
+    cache::enable(std::env::temp_dir().join("aoc-bench-cache"));
+    let mut group = c.benchmark_group("day5");
+    let inputs = [
+        ("example", include_str!("../../examples/day5/almanac.txt").to_owned()),
+        ("generated", day5_input(100, 200)),
+    ];
+    for (name, input) in inputs.iter() {
+        group.bench_with_input(BenchmarkId::new("parse_almanac2", name), input, |b, input| {
+            b.iter(|| day5::parse_almanac2(black_box(input)).unwrap())
+        });
+        // a cache hit after the first iteration, what `--cache` saves on later runs
+        group.bench_with_input(BenchmarkId::new("cached_almanac", name), input, |b, input| {
+            b.iter(|| {
+                let source = include_str!("../src/commands/day5.rs");
+                cache::parsed("day5", source, black_box(input), day5::parse_almanac2).unwrap()
+            })
+        });
+        let almanac = day5::parse_almanac2(input).unwrap();
+        group.bench_with_input(BenchmarkId::new("get_seeds", name), &almanac, |b, almanac| {
+            b.iter(|| day5::get_seeds(black_box(almanac.seeds.clone())))
+        });
+        let seeds = day5::get_seeds(almanac.seeds.clone());
+        group.bench_with_input(BenchmarkId::new("stages", name), &almanac, |b, almanac| {
+            b.iter(|| day5::stages(black_box(seeds.clone()), &almanac.maps))
+        });
+    }
+    group.finish();
+}
+
+// a multi-megabyte input read from disk, `read_input` and the `&str` parser against the bytes
+fn bench_day1_large(c: &mut Criterion) {
+    use aoc::utils::{bytes::Input, read_input};
+
+    let mut group = c.benchmark_group("large");
+    group.sample_size(10);
+    let day1_path = std::env::temp_dir().join("aoc-bench-day1.txt");
+    fs::write(&day1_path, day1_input(500_000)).unwrap();
+    group.throughput(file_size(&day1_path));
+    group.bench_function(BenchmarkId::new("day1_part2", "str"), |b| {
+        b.iter(|| day1::part2(&read_input(black_box(&day1_path)).unwrap()).unwrap())
+    });
+    group.bench_function(BenchmarkId::new("day1_part2", "bytes"), |b| {
+        b.iter(|| day1::part2_bytes(&Input::open(black_box(&day1_path)).unwrap()).unwrap())
+    });
+    group.finish();
+    fs::remove_file(day1_path).unwrap();
+}
+
+// the same for a field, the byte path borrowing its tiles
+fn bench_day10_large(c: &mut Criterion) {
+    use aoc::utils::{bytes::Input, parse::complete, read_input};
+
+    let mut group = c.benchmark_group("large");
+    group.sample_size(10);
+    let day10_path = std::env::temp_dir().join("aoc-bench-day10.txt");
+    fs::write(&day10_path, day10_input(2_000)).unwrap();
+    group.throughput(file_size(&day10_path));
+    group.bench_function(BenchmarkId::new("day10_parse_field", "str"), |b| {
+        b.iter(|| {
+            let input = read_input(black_box(&day10_path)).unwrap();
+            complete(&input, day10::parse_field).unwrap()
+        })
+    });
+    group.bench_function(BenchmarkId::new("day10_parse_field", "bytes"), |b| {
+        b.iter(|| {
+            let input = Input::open(black_box(&day10_path)).unwrap();
+            black_box(day10::parse_field_bytes(&input).unwrap());
+        })
+    });
+    group.finish();
+    fs::remove_file(day10_path).unwrap();
+}
+
+fn file_size(path: &Path) -> Throughput {
+    Throughput::Bytes(fs::metadata(path).unwrap().len())
+}
+
+criterion_group!(benches, bench_day1, bench_day10, bench_day5, bench_day1_large, bench_day10_large);
+criterion_main!(benches);
@@ -0,0 +1,73 @@
+use std::{
+    env,
+    fmt::Write,
+    fs,
+    path::{Path, PathBuf},
+};
+
+// Generates one test per example file and part from `examples/<day>/<name>.txt`
+// and the expected answers recorded next to it in `examples/<day>/<name>.answers`.
+fn main() {
+    let examples: PathBuf = Path::new(env!("CARGO_MANIFEST_DIR")).join("..").join("examples");
+    println!("cargo:rerun-if-changed={}", examples.display());
+
+    let mut tests = String::new();
+    for day in sorted_entries(&examples).into_iter().filter(|p| p.is_dir()) {
+        let day_name = file_stem(&day);
+        for example in sorted_entries(&day) {
+            if example.extension().is_none_or(|e| e != "txt") {
+                continue;
+            }
+            let answers_path = example.with_extension("answers");
+            let Ok(answers) = fs::read_to_string(&answers_path) else {
+                println!("cargo:warning=no answers recorded for {}", example.display());
+                continue;
+            };
+            let example_name = file_stem(&example);
+            for (part, expected) in parse_answers(&answers) {
+                writeln!(
+                    tests,
+                    "#[test]\nfn {}_{}_{part}() -> Result<(), AocError> {{\n    \
+                     check_example({:?}, {part:?}, {expected:?}, aoc::commands::{day_name}::{part})\n}}\n",
+                    identifier(&day_name),
+                    identifier(&example_name),
+                    format!("{day_name}/{example_name}.txt"),
+                )
+                .unwrap();
+            }
+        }
+    }
+
+    let out: PathBuf = PathBuf::from(env::var("OUT_DIR").unwrap()).join("examples.rs");
+    fs::write(out, tests).unwrap();
+}
+
+fn sorted_entries(dir: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return vec![];
+    };
+    let mut paths: Vec<PathBuf> = entries.filter_map(|e| e.ok()).map(|e| e.path()).collect();
+    paths.sort();
+    paths
+}
+
+fn file_stem(path: &Path) -> String {
+    path.file_stem().unwrap().to_string_lossy().into_owned()
+}
+
+// `part1: 142` per line; blank lines and `#` comments are ignored
+fn parse_answers(answers: &str) -> Vec<(String, String)> {
+    answers
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .filter_map(|line| line.split_once(':'))
+        .map(|(part, expected)| (part.trim().to_owned(), expected.trim().to_owned()))
+        .collect()
+}
+
+fn identifier(name: &str) -> String {
+    name.chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c.to_ascii_lowercase() } else { '_' })
+        .collect()
+}
@@ -0,0 +1,11 @@
+#![no_main]
+
+use aoc::commands::day10::{find_loop, parse_field};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| {
+    // a field without a start must be an error from the loop search too
+    if let Ok((_, field)) = parse_field(input) {
+        let _ = find_loop(&field);
+    }
+});
@@ -0,0 +1,9 @@
+#![no_main]
+
+use aoc::commands::day1::{extract_digits, extract_numbers};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| {
+    let _ = extract_digits(input);
+    let _ = extract_numbers(input);
+});
@@ -0,0 +1,14 @@
+#![no_main]
+
+use aoc::commands::day5;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| {
+    if let Ok(almanac) = day5::parse_almanac2(input) {
+        let _ = day5::stages(day5::get_seeds(almanac.seeds), &almanac.maps);
+    }
+    if let Ok((_, seeds)) = day5::parse_seeds(input) {
+        let _ = day5::get_seeds(seeds);
+    }
+    let _ = day5::parse_map(input);
+});
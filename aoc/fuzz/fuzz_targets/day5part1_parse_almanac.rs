@@ -0,0 +1,11 @@
+#![no_main]
+
+use aoc::commands::day5part1;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| {
+    // part two walks every seed, so only the part one pipeline is fuzzed
+    let _ = day5part1::parse_almanac(input);
+    let _ = day5part1::parse_seeds(input);
+    let _ = day5part1::parse_map(input);
+});
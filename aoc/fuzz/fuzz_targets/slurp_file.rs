@@ -0,0 +1,21 @@
+#![no_main]
+
+use std::{env, fs, process};
+
+use aoc::utils::{slurp_all, slurp_file};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let path = env::temp_dir().join(format!("aoc-fuzz-slurp-{}", process::id()));
+    fs::write(&path, data).unwrap();
+    let first = slurp_file::<_, u32>(&path);
+    let all = slurp_all::<_, u32>(&path);
+    // the first error of the collecting mode is the one slurp_file stops at
+    match (first, all) {
+        (Ok(a), Ok(b)) => assert_eq!(a, b),
+        (Err(e), Err(errors)) => assert_eq!(e.line(), errors.0[0].line()),
+        _ => panic!("slurp_file and slurp_all disagree"),
+    }
+    let _ = slurp_file::<_, i64>(&path);
+    let _ = slurp_file::<_, String>(&path);
+});
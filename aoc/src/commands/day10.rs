@@ -2,21 +2,22 @@ use std::path::PathBuf;
 
 use clap::Parser;
 
-use super::{CommandImpl, DynError};
-
-use std::fs::read_to_string;
-
-use intersection::hash_set;
-use nom::{
-    bytes::complete::tag,
-    character::complete::{alphanumeric1, i32, newline, one_of, space1},
-    multi::{many1, separated_list1},
-    sequence::separated_pair,
-    IResult,
+use super::CommandImpl;
+use crate::error::AocError;
+
+use crate::utils::{
+    bytes::ByteGrid,
+    cache,
+    grid::{Cells, Grid},
+    parse::{complete, grid},
+    point::{Direction, Point},
+    read_input,
+    search::{bfs, dfs},
 };
-use std::collections::HashMap;
+use nom::IResult;
+use serde::{Deserialize, Serialize};
 use std::collections::HashSet;
-use std::collections::VecDeque;
+use std::fmt;
 
 #[derive(Parser, Debug)]
 pub struct Day10 {
@@ -24,7 +25,7 @@ pub struct Day10 {
     input: PathBuf,
 }
 
-#[derive(Debug, Eq, PartialEq, Hash, Copy, Clone)]
+#[derive(Debug, Eq, PartialEq, Hash, Copy, Clone, Serialize, Deserialize)]
 pub enum Pipe {
     Start,
     Vertical,
@@ -36,18 +37,28 @@ pub enum Pipe {
     None,
 }
 
+impl TryFrom<char> for Pipe {
+    type Error = AocError;
+
+    fn try_from(c: char) -> Result<Self, Self::Error> {
+        let pipe = u8::try_from(c).ok().and_then(Pipe::from_byte);
+        pipe.copied().ok_or_else(|| AocError::parse(format!("unexpected pipe {c:?}")))
+    }
+}
+
 impl Pipe {
-    pub fn new(c: char) -> Pipe {
-        match c {
-            'S' => Pipe::Start,
-            '|' => Pipe::Vertical,
-            '-' => Pipe::Horizontal,
-            'L' => Pipe::NorthEast,
-            'J' => Pipe::NorthWest,
-            '7' => Pipe::SouthWest,
-            'F' => Pipe::SouthEast,
-            '.' => Pipe::None,
-            _ => panic!(),
+    // a `'static` pipe, so a field read in place can hand out references to its tiles
+    fn from_byte(b: u8) -> Option<&'static Pipe> {
+        match b {
+            b'S' => Some(&Pipe::Start),
+            b'|' => Some(&Pipe::Vertical),
+            b'-' => Some(&Pipe::Horizontal),
+            b'L' => Some(&Pipe::NorthEast),
+            b'J' => Some(&Pipe::NorthWest),
+            b'7' => Some(&Pipe::SouthWest),
+            b'F' => Some(&Pipe::SouthEast),
+            b'.' => Some(&Pipe::None),
+            _ => None,
         }
     }
 
@@ -55,283 +66,182 @@ impl Pipe {
         *self == Pipe::NorthEast || *self == Pipe::SouthWest
     }
 
-    pub fn neighbors(&self, field: &Vec<Vec<Pipe>>, pos: (usize, usize)) -> Vec<(usize, usize)> {
-        let mut vec: Vec<(usize, usize)> = Vec::new();
-        match *self {
-            Pipe::Vertical | Pipe::Start | Pipe::NorthEast | Pipe::NorthWest => {
-                if pos.0 > 0usize {
-                    let upper_pipe: Pipe = field[pos.0 - 1][pos.1];
-                    if connects_vertical(upper_pipe, *self) {
-                        //println!("add left neighbor");
-                        vec.push((pos.0 - 1, pos.1));
-                    } else {
-                        //println!("upper pipe incompatible with {self:#?}");
-                    };
-                } else {
-                    //println!("skip upper pipe because {pos:#?}");
-                };
-            }
-            _ => {}
-        };
-        match *self {
-            Pipe::Vertical | Pipe::Start | Pipe::SouthEast | Pipe::SouthWest => {
-                //println!("right vertical check");
-                if pos.0 < field.len() - 1 {
-                    let lower_pipe: Pipe = field[pos.0 + 1][pos.1];
-                    if connects_vertical(*self, lower_pipe) {
-                        //println!("add right neighbor");
-                        vec.push((pos.0 + 1, pos.1));
-                    } else {
-                        //println!("lower pipe incompatible with {self:#?}");
-                    };
-                } else {
-                    //println!("skip lower pipe below because {pos:#?}");
-                };
-            }
-            _ => {}
-        };
-
-        match *self {
-            Pipe::Horizontal | Pipe::Start | Pipe::NorthWest | Pipe::SouthWest => {
-                //println!("upper horizontal check");
-                if pos.1 > 0 {
-                    let left_pipe: Pipe = field[pos.0][pos.1 - 1];
-                    if connects_horizontal(left_pipe, *self) {
-                        // println!("add lower neighbor");
-                        vec.push((pos.0, pos.1 - 1));
-                    } else {
-                        //println!("left pipe incompatible with {self:#?}");
-                    };
-                } else {
-                    //println!("skip left because {pos:#?}");
-                };
-            }
-            _ => {}
-        };
-        match *self {
-            Pipe::Horizontal | Pipe::Start | Pipe::NorthEast | Pipe::SouthEast => {
-                //println!("horizontal lower check");
-                if pos.1 < field[0].len() - 1 {
-                    let right_pipe: Pipe = field[pos.0][pos.1 + 1];
-                    if connects_horizontal(*self, right_pipe) {
-                        //println!("add upper neighbor");
-                        vec.push((pos.0, pos.1 + 1));
-                    } else {
-                        //println!("right pipe incompatible with {self:#?}");
-                    };
-                } else {
-                    println!("skip right because {pos:#?}");
-                };
-            }
-            _ => {}
-        };
-        vec
+    // the directions this pipe leads to, every one of them for the start
+    pub fn openings(&self) -> &'static [Direction] {
+        use Direction::*;
+        match self {
+            Pipe::Start => &[North, East, South, West],
+            Pipe::Vertical => &[North, South],
+            Pipe::Horizontal => &[East, West],
+            Pipe::NorthEast => &[North, East],
+            Pipe::NorthWest => &[North, West],
+            Pipe::SouthWest => &[South, West],
+            Pipe::SouthEast => &[South, East],
+            Pipe::None => &[],
+        }
+    }
+
+    // the neighbouring pipes this one connects to, each has to lead back to it
+    pub fn neighbors(&self, field: &impl Cells<Cell = Pipe>, point: Point) -> Vec<Point> {
+        self.openings()
+            .iter()
+            .filter_map(|direction| {
+                let next = point + *direction;
+                let leads_back = field.get(next)?.openings().contains(&direction.opposite());
+                leads_back.then_some(next)
+            })
+            .collect()
     }
 }
-pub fn connects_vertical(top_pipe: Pipe, bottom_pipe: Pipe) -> bool {
-    let mut upper: HashSet<Pipe> = HashSet::new();
-    let mut lower: HashSet<Pipe> = HashSet::new();
 
-    upper.insert(Pipe::Start);
-    upper.insert(Pipe::Vertical);
-    upper.insert(Pipe::SouthEast);
-    upper.insert(Pipe::SouthWest);
+impl fmt::Display for Pipe {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", char::from(*self))
+    }
+}
 
-    lower.insert(Pipe::Start);
-    lower.insert(Pipe::Vertical);
-    lower.insert(Pipe::NorthEast);
-    lower.insert(Pipe::NorthWest);
+impl From<Pipe> for char {
+    fn from(pipe: Pipe) -> char {
+        match pipe {
+            Pipe::Start => 'S',
+            Pipe::Vertical => '|',
+            Pipe::Horizontal => '-',
+            Pipe::NorthEast => 'L',
+            Pipe::NorthWest => 'J',
+            Pipe::SouthWest => '7',
+            Pipe::SouthEast => 'F',
+            Pipe::None => '.',
+        }
+    }
+}
 
-    upper.contains(&top_pipe) && lower.contains(&bottom_pipe)
+pub fn parse_field(input: &str) -> IResult<&str, Grid<Pipe>> {
+    grid(input)
 }
 
-pub fn connects_horizontal(left_pipe: Pipe, right_pipe: Pipe) -> bool {
-    let mut lhs: HashSet<Pipe> = HashSet::new();
-    let mut rhs: HashSet<Pipe> = HashSet::new();
+/// A field read in place from the bytes of an input, every tile checked to be a pipe.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PipeField<'a>(ByteGrid<'a>);
 
-    lhs.insert(Pipe::Start);
-    lhs.insert(Pipe::Horizontal);
-    lhs.insert(Pipe::NorthEast);
-    lhs.insert(Pipe::SouthEast);
+impl Cells for PipeField<'_> {
+    type Cell = Pipe;
 
-    rhs.insert(Pipe::Start);
-    rhs.insert(Pipe::Horizontal);
-    rhs.insert(Pipe::NorthWest);
-    rhs.insert(Pipe::SouthWest);
+    fn get(&self, point: Point) -> Option<&Pipe> {
+        let (col, row) = point.to_index()?;
+        Pipe::from_byte(self.0.get(row, col)?)
+    }
 
-    lhs.contains(&left_pipe) && rhs.contains(&right_pipe)
+    fn bounds(&self) -> Option<(Point, Point)> {
+        let max =
+            Point::from_index(self.0.width().checked_sub(1)?, self.0.height().checked_sub(1)?)?;
+        Some((Point::ORIGIN, max))
+    }
 }
 
-fn parse_row(input: &str) -> IResult<&str, Vec<Pipe>> {
-    let (input, sequence) = many1(one_of("S|-LJ7F."))(input)?;
-    let pipes: Vec<Pipe> = sequence.iter().map(|s| Pipe::new(*s)).collect::<Vec<Pipe>>();
-    Ok((input, pipes))
+/// [`parse_field`] on the raw bytes of an input, see [`crate::utils::bytes::Input`]. The
+/// field borrows from them instead of copying the tiles.
+pub fn parse_field_bytes(input: &[u8]) -> Result<PipeField<'_>, AocError> {
+    let grid = ByteGrid::new(input)?;
+    grid.check(|b| {
+        Pipe::from_byte(b).ok_or_else(|| format!("unexpected pipe {:?}", char::from(b)))
+    })?;
+    Ok(PipeField(grid))
 }
 
-fn parse_field(input: &str) -> IResult<&str, Vec<Vec<Pipe>>> {
-    let (input, field) = separated_list1(newline, parse_row)(input)?;
-
-    Ok((input, field))
+pub fn render_field(field: &Grid<Pipe>) -> String {
+    field.to_string()
 }
 
-pub fn find_start(field: &Vec<Vec<Pipe>>) -> Option<(usize, usize)> {
-    for (i, r) in field.iter().enumerate() {
-        for (j, p) in r.iter().enumerate() {
-            if *p == Pipe::Start {
-                return Some((i, j));
-            }
-        }
+// draw only the tiles belonging to the loop
+pub fn render_loop(field: &Grid<Pipe>, pipes: &HashSet<Point>) -> String {
+    let mut rendered = field.map(|p| char::from(*p));
+    for point in field.points().filter(|point| !pipes.contains(point)) {
+        rendered[point] = '.';
     }
-    None
+    rendered.to_string()
 }
 
-pub fn exterior_pipes(field: &Vec<Vec<Pipe>>, pipes: &HashSet<(usize, usize)>) -> usize {
-    let start: (usize, usize) = (0, 0);
-    let mut stack: Vec<(usize, usize)> = vec![start];
-    let mut nexterior_pipes: usize = 0usize;
-    let mut visited: HashSet<(usize, usize)> = HashSet::new();
-    for i in 0..field[0].len() {
-        let pos: (usize, usize) = (0, i);
-        if !pipes.contains(&pos) && !visited.contains(&pos) {
-            stack.push(pos);
-            visited.insert(pos);
-        }
-        let pos: (usize, usize) = (field.len() - 1, i);
-        if !pipes.contains(&pos) && !visited.contains(&pos) {
-            stack.push(pos);
-            visited.insert(pos);
-        }
-    }
-    for i in 1..field.len() - 1 {
-        let pos: (usize, usize) = (i, 0);
-        if !pipes.contains(&pos) && !visited.contains(&pos) {
-            stack.push(pos);
-            visited.insert(pos);
-        }
-        let pos: (usize, usize) = (i, field[0].len() - 1);
-        if !pipes.contains(&pos) && !visited.contains(&pos) {
-            stack.push(pos);
-            visited.insert(pos);
-        }
-    }
-
-    while !stack.is_empty() {
-        let Some(pos) = stack.pop() else {
-            println!("something funky happened");
-            break;
-        };
-        nexterior_pipes += 1;
-        if !pipes.contains(&pos) {
-            if pos.0 > 0 && !visited.contains(&(pos.0 - 1, pos.1)) {
-                let pos: (usize, usize) = (pos.0 - 1, pos.1);
-                stack.push(pos);
-                visited.insert(pos);
-            }
-            if pos.0 < field.len() - 1 && !visited.contains(&(pos.0 + 1, pos.1)) {
-                let pos: (usize, usize) = (pos.0 + 1, pos.1);
-                stack.push(pos);
-                visited.insert(pos);
-            }
-            if pos.1 > 0 && !visited.contains(&(pos.0, pos.1 - 1)) {
-                let pos: (usize, usize) = (pos.0, pos.1 - 1);
-                stack.push(pos);
-                visited.insert(pos);
-            }
-            if pos.1 < field[0].len() && !visited.contains(&(pos.0, pos.1 + 1)) {
-                let pos: (usize, usize) = (pos.0, pos.1 + 1);
-                stack.push(pos);
-                visited.insert(pos);
-            }
-        }
-    }
-    visited.len()
+pub fn find_start(field: &impl Cells<Cell = Pipe>) -> Option<Point> {
+    field.points().find(|point| field.get(*point) == Some(&Pipe::Start))
 }
 
-pub fn find_loop(field: &Vec<Vec<Pipe>>) -> (usize, HashSet<(usize, usize)>) {
-    let mut queue: VecDeque<(usize, Pipe, (usize, usize))> = VecDeque::new();
-    let mut visited: HashSet<(usize, usize)> = HashSet::new();
-    let Some(s) = find_start(&field) else {
-        panic!("no start found");
-    };
-    let mut max_steps: usize = 0;
-
-    queue.push_back((0, Pipe::Start, s));
-    while !queue.is_empty() {
-        let Some((step, pipe, pos)) = queue.pop_front() else {
-            panic!("unexpected pop");
-        };
-        visited.insert(pos);
-        if step > max_steps {
-            max_steps = step;
-        };
-        println!("step: {step} popped: {pipe:?} at {pos:?}");
-        for neighbor in pipe.neighbors(&field, pos) {
-            if !visited.contains(&neighbor) {
-                let (x, y): (usize, usize) = neighbor;
-                let new_pipe: Pipe = field[x][y];
-                println!("step: {step}: push {new_pipe:#?} at {neighbor:?}");
-                queue.push_back((step + 1, new_pipe, neighbor));
-            }
-        }
-    }
+// flood fill the tiles outside the loop reachable from the border
+pub fn exterior_pipes(field: &impl Cells<Cell = Pipe>, pipes: &HashSet<Point>) -> usize {
+    let on_border = |point: Point| point.neighbors4().iter().any(|next| !field.contains(*next));
+    let border = field.points().filter(|point| on_border(*point) && !pipes.contains(point));
+    let outside =
+        dfs(border, |point| field.neighbors4(*point).filter(|next| !pipes.contains(next)));
+    outside.len()
+}
 
-    (max_steps, visited)
+// walk the loop both ways from the start, the farthest tile is where the two walks meet
+pub fn find_loop(field: &impl Cells<Cell = Pipe>) -> Result<(usize, HashSet<Point>), AocError> {
+    let start = find_start(field).ok_or_else(|| AocError::unsolvable("unable to find start"))?;
+    let walk = bfs([start], |point| {
+        field.get(*point).map_or_else(Vec::new, |pipe| pipe.neighbors(field, *point))
+    });
+    Ok((walk.max_distance().unwrap_or_default(), walk.distances.into_keys().collect()))
 }
 
-pub fn count_interior_positions(field: &Vec<Vec<Pipe>>, pipe: HashSet<(usize, usize)>) -> usize {
-    let mut interior_positions: Vec<(usize, usize)> = Vec::new();
+// a tile is inside when a diagonal ray towards the top left crosses the loop an odd number
+// of times, the corners it only grazes do not count
+pub fn count_interior_positions(field: &impl Cells<Cell = Pipe>, pipe: HashSet<Point>) -> usize {
+    let towards_origin = Direction::North.delta() + Direction::West.delta();
     let mut ninterior: usize = 0;
-    for x in 0..field.len() {
-        for y in 0..field[0].len() {
-            if pipe.contains(&(x, y)) {
-                continue;
-            }
-            let (mut newx, mut newy) = (x, y);
-            let mut ncrossings: usize = 0;
-
-            while newx < field.len() && newy >= 0 {
-                let pos: (usize, usize) = (newx, newy);
-                let p: Pipe = field[newx][newy];
-                let is_corner: bool = p.is_corner();
-                if pipe.contains(&pos) && !is_corner {
-                    ncrossings += 1;
-                }
-                if newx == 0 || newy == 0 {
-                    break;
-                }
-                newx = newx - 1;
-                newy = newy - 1;
-            }
-            if ncrossings.rem_euclid(2) == 1 {
-                ninterior += 1;
+    for point in field.points().filter(|point| !pipe.contains(point)) {
+        let mut ncrossings: usize = 0;
+        let mut ray = point;
+        while let Some(tile) = field.get(ray) {
+            if pipe.contains(&ray) && !tile.is_corner() {
+                ncrossings += 1;
             }
+            ray += towards_origin;
+        }
+        if ncrossings % 2 == 1 {
+            ninterior += 1;
         }
     }
 
     ninterior
 }
 
+fn farthest_step(field: &impl Cells<Cell = Pipe>) -> Result<usize, AocError> {
+    let (max_steps, _) = find_loop(field)?;
+    Ok(max_steps)
+}
+
+fn enclosed_tiles(field: &impl Cells<Cell = Pipe>) -> Result<usize, AocError> {
+    let (_, pipe_positions) = find_loop(field)?;
+    Ok(count_interior_positions(field, pipe_positions))
+}
+
+// the parsed field, loaded from the cache when `--cache` is on
+fn field(input: &str) -> Result<Grid<Pipe>, AocError> {
+    cache::parsed("day10", include_str!("day10.rs"), input, |input| complete(input, parse_field))
+}
+
+pub fn part1(input: &str) -> Result<usize, AocError> {
+    farthest_step(&field(input)?)
+}
+
+pub fn part2(input: &str) -> Result<usize, AocError> {
+    enclosed_tiles(&field(input)?)
+}
+
+pub fn part1_bytes(input: &[u8]) -> Result<usize, AocError> {
+    farthest_step(&parse_field_bytes(input)?)
+}
+
+pub fn part2_bytes(input: &[u8]) -> Result<usize, AocError> {
+    enclosed_tiles(&parse_field_bytes(input)?)
+}
+
 impl CommandImpl for Day10 {
-    fn main(&self) -> Result<(), DynError> {
-        let string = read_to_string(&self.input).unwrap();
-        let (_, field) = parse_field(&string).unwrap();
-        let Some(start) = find_start(&field) else {
-            println!("unable to find start");
-            return Ok(());
-        };
-        println!("field: {field:?}");
-        let (max_steps, pipe_positions) = find_loop(&field);
-        println!("pipes are at {pipe_positions:?}");
-        println!("there are {:?} pipes in the loop", pipe_positions.len());
-        let ninterior = count_interior_positions(&field, pipe_positions);
-        //println!("sequences {sequences:#?}");
-        //let solution: i32 = solve_parta(sequences);
-        //println!("solution a: {solution:#?}");
-        //println!("start: {start:#?}");
-        //println!("max steps: {max_steps}");
-        println!("n interior pipes: {ninterior}");
-        //let field_dimension = field.len() * field[0].len();
-        //println!("field dimension: {field_dimension}");
+    fn main(&self) -> Result<(), AocError> {
+        let string = read_input(&self.input)?;
+        println!("part 1: {}", part1(&string)?);
+        println!("part 2: {}", part2(&string)?);
+
         Ok(())
     }
 }
@@ -339,18 +249,83 @@ impl CommandImpl for Day10 {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::utils::bytes::Input;
+    use std::{fs, path::Path};
 
     #[test]
     fn test_range_overlaps() {
-        //(&self, pos: (usize, usize), dim: (usize, usize)) -> Vec<(usize, usize)> {
         let pipe: Pipe = Pipe::Start;
-        let pos: (usize, usize) = (0, 0);
-        let field: Vec<Vec<Pipe>> = vec![
+        let start: Point = Point::ORIGIN;
+        let field: Grid<Pipe> = Grid::from_rows(vec![
             vec![Pipe::Start, Pipe::Horizontal, Pipe::SouthWest],
             vec![Pipe::Vertical, Pipe::None, Pipe::Vertical],
             vec![Pipe::NorthEast, Pipe::Horizontal, Pipe::NorthWest],
-        ];
-        let neighbors: Vec<(usize, usize)> = pipe.neighbors(&field, pos);
-        assert_eq!(2usize, neighbors.len());
+        ])
+        .unwrap();
+        let neighbors: Vec<Point> = pipe.neighbors(&field, start);
+        assert_eq!(neighbors, [Point::new(1, 0), Point::new(0, 1)]);
+        let right = Point::new(2, 1);
+        assert_eq!(Pipe::Vertical.neighbors(&field, right), [Point::new(2, 0), Point::new(2, 2)]);
+        assert!(Pipe::None.neighbors(&field, Point::new(1, 1)).is_empty());
+        assert_eq!(exterior_pipes(&field, &HashSet::from([start])), 8);
+    }
+
+    #[test]
+    fn test_parse_field_errors() {
+        assert!(Pipe::try_from('X').is_err());
+        assert!(parse_field("X-7").is_err());
+        assert!(parse_field("S-7\n|\n").is_err());
+    }
+
+    // every example through both paths, the byte path has to normalize like `read_input`
+    #[test]
+    fn test_parse_field_bytes() {
+        let examples = Path::new(env!("CARGO_MANIFEST_DIR")).join("../examples/day10");
+        for entry in fs::read_dir(examples).unwrap() {
+            let path = entry.unwrap().path();
+            if path.extension().is_none_or(|e| e != "txt") {
+                continue;
+            }
+            let string = read_input(&path).unwrap();
+            let bytes = Input::open(&path).unwrap();
+            let grid = complete(&string, parse_field).unwrap();
+            let field =
+                parse_field_bytes(&bytes).unwrap_or_else(|e| panic!("{}: {e}", path.display()));
+            assert_eq!(field.bounds(), grid.bounds(), "{}", path.display());
+            assert!(grid.iter().all(|(point, pipe)| field.get(point) == Some(pipe)));
+            assert_eq!(part1_bytes(&bytes).ok(), part1(&string).ok(), "{}", path.display());
+            assert_eq!(part2_bytes(&bytes).ok(), part2(&string).ok(), "{}", path.display());
+        }
+        let error = parse_field_bytes(b"S-7\n|X|\n").unwrap_err();
+        assert!(error.to_string().starts_with("parse error at line 2, column 2"));
+    }
+
+    #[test]
+    fn test_missing_start_is_unsolvable() {
+        let (_, field) = parse_field("-7\nLJ\n").unwrap();
+        assert!(matches!(find_loop(&field), Err(AocError::Unsolvable(_))));
+        assert_eq!(part1("-7\nLJ\n").unwrap_err().exit_code(), 6);
+    }
+
+    #[test]
+    fn test_leftover_input_is_located() {
+        let error = part1("S-7\n|.|\nL-J\n\nF-7\n").unwrap_err();
+        assert_eq!(error.line(), Some(5));
+    }
+
+    #[test]
+    fn test_render_field() {
+        let input: &str = include_str!("../../../examples/day10/larger.txt");
+        let (_, field) = parse_field(input).unwrap();
+        crate::assert_snapshot!("day10_larger_field", render_field(&field));
+    }
+
+    // complex.txt has pipes off the loop, which the rendering must leave out
+    #[test]
+    fn test_loop_membership() {
+        let input: &str = include_str!("../../../examples/day10/complex.txt");
+        let (_, field) = parse_field(input).unwrap();
+        let (_, pipes) = find_loop(&field).unwrap();
+        crate::assert_snapshot!("day10_complex_loop", render_loop(&field, &pipes));
     }
 }
@@ -2,25 +2,24 @@ use std::path::PathBuf;
 
 use clap::Parser;
 
-use super::{CommandImpl, DynError};
-
-use std::fs::read_to_string;
+use super::CommandImpl;
+use crate::error::AocError;
 
 use std::cmp::Ordering;
+use std::fmt;
 
-use intersection::hash_set;
+use crate::utils::{
+    cache,
+    parse::{lines, numbers},
+    read_input, sections,
+};
 use nom::{
-    branch::alt,
-    bytes::complete::{tag, take_until},
-    character::complete::{alpha0, alphanumeric1, digit1, newline, space0, space1, u32},
-    multi::{many0, separated_list1},
-    sequence::{delimited, preceded, separated_pair},
+    bytes::complete::tag,
+    character::complete::{space1, u32},
+    sequence::{preceded, tuple},
     IResult,
 };
-use std::collections::HashMap;
-use std::collections::HashSet;
-use std::fs::File;
-use std::io::{self, prelude::*, BufReader};
+use serde::{Deserialize, Serialize};
 
 #[derive(Parser, Debug)]
 pub struct Day5 {
@@ -28,116 +27,169 @@ pub struct Day5 {
     input: PathBuf,
 }
 
-fn parse_range(input: &str) -> IResult<&str, InclusiveRange> {
-    let (input, soil_map) = separated_list1(space1, u32)(input)?;
-    let offset: usize = soil_map[0] as usize;
-    let range: InclusiveRange =
-        InclusiveRange::new(soil_map[1] as usize, soil_map[2] as usize, offset);
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Almanac {
+    pub seeds: Vec<u32>,
+    pub maps: Vec<Vec<HalfOpenRange>>,
+}
+
+// `destination source length`, projecting the source range onto the destination
+fn parse_range(input: &str) -> IResult<&str, HalfOpenRange> {
+    let (input, (destination, source, length)) =
+        tuple((u32, preceded(space1, u32), preceded(space1, u32)))(input)?;
+    let (destination, source) = (destination as usize, source as usize);
+    let mut range: HalfOpenRange = HalfOpenRange::new(source, length as usize);
+    if destination >= source {
+        range.set_addition(destination - source);
+    } else {
+        range.set_subtraction(source - destination);
+    }
     Ok((input, range))
 }
 
-fn parse_map(input: &str) -> IResult<&str, Vec<InclusiveRange>> {
-    let (input, soil_maps) = separated_list1(newline, parse_range)(input)?;
-    Ok((input, soil_maps))
+pub fn parse_map(input: &str) -> IResult<&str, Vec<HalfOpenRange>> {
+    lines(parse_range)(input)
 }
 
-fn parse_seeds(input: &str) -> IResult<&str, Vec<u32>> {
-    let (input, _) = tag("seeds:")(input)?;
-    let (input, _) = space1(input)?;
-    let (input, mut seeds) = separated_list1(space1, u32)(input)?;
-    let coalesced_seeds: Vec<InclusiveRange> = Vec::new();
-    Ok((input, seeds))
+pub fn parse_seeds(input: &str) -> IResult<&str, Vec<u32>> {
+    preceded(tag("seeds:"), numbers)(input)
 }
 
-pub fn keep(
-    source: &Vec<InclusiveRange>,
-    mymap: (usize, Vec<InclusiveRange>),
-) -> Vec<InclusiveRange> {
-    todo!()
+// a `seeds:` section followed by the maps in the order they are applied
+pub fn parse_almanac2(input: &str) -> Result<Almanac, AocError> {
+    let sections = sections(input);
+    let Some((seeds, maps)) = sections.split_first() else {
+        return Err(AocError::parse("empty almanac"));
+    };
+    if seeds.header != Some("seeds") {
+        return Err(AocError::parse("the almanac does not start with seeds:"));
+    }
+    let seeds: Vec<u32> = seeds.parse(numbers)?.concat();
+    let maps = maps.iter().map(|map| map.parse(parse_range)).collect::<Result<_, _>>()?;
+    Ok(Almanac { seeds, maps })
 }
 
-pub fn get_seeds(seeds: Vec<u32>) -> Vec<InclusiveRange> {
-    let mut i = 0;
-    let mut newseeds: Vec<InclusiveRange> = Vec::new();
-    while i < seeds.len() - 1 {
-        let start: usize = seeds[i] as usize;
-        let length: usize = seeds[i + 1] as usize;
-        let range: InclusiveRange = InclusiveRange::new(start, length, 0);
-        newseeds.push(range);
-        i += 2;
-    }
-    newseeds.sort();
-    let mut coalesced_seeds: Vec<InclusiveRange> = Vec::new();
-    while !newseeds.is_empty() {
-        let mut top = newseeds.pop().unwrap();
-        loop {
-            if !newseeds.is_empty() && top.overlaps(&newseeds[0]) {
-                let newtop = newseeds.pop().unwrap();
-                top = top.coalesce(&newtop).unwrap();
-            } else {
-                coalesced_seeds.push(top);
-                break;
+// send every range through one map; portions not covered by the map keep their value.
+// The result is not coalesced, so the total length of the ranges is preserved.
+pub fn keep(source: &[HalfOpenRange], mymap: &[HalfOpenRange]) -> Vec<HalfOpenRange> {
+    let mut unmapped: Vec<HalfOpenRange> = source.to_vec();
+    let mut destination: Vec<HalfOpenRange> = Vec::new();
+    for m in mymap {
+        let mut remaining: Vec<HalfOpenRange> = Vec::new();
+        for s in unmapped {
+            if !s.overlaps(m) {
+                remaining.push(s);
+                continue;
             }
+            destination.extend(m.conjunction(&s));
+            remaining.extend(s.lower_disjunction(m));
+            remaining.extend(s.upper_disjunction(m));
         }
+        unmapped = remaining;
     }
-    coalesced_seeds
+    destination.extend(unmapped);
+    destination
 }
 
-fn parse_almanac2(input: &str) -> IResult<&str, usize> {
-    let (input, seeds) = parse_seeds(input)?;
-    let mut seeds: Vec<InclusiveRange> = get_seeds(seeds);
-
-    let tags: Vec<String> = vec![
-        "seed-to-soil map:".to_owned(),
-        "soil-to-fertilizer map:".to_owned(),
-        "fertilizer-to-water map:".to_owned(),
-        "water-to-light map:".to_owned(),
-        "light-to-temperature map:".to_owned(),
-        "temperature-to-humidity map:".to_owned(),
-        "humidity-to-location map:".to_owned(),
-    ];
-
-    let mut maps: Vec<Vec<InclusiveRange>> = vec![];
-    for mytag in tags.iter() {
-        let (input, _) = many0(newline)(input)?;
-        let (input, _) = take_until(":")(input)?;
-        let (input, _) = tag(":")(input)?;
-        let (input, _) = many0(newline)(input)?;
-        let (input, mut seed_to_soil) = parse_map(input)?;
-        let (input, _) = many0(newline)(input)?;
-        println!("parse {mytag:?}");
-        maps.push(seed_to_soil);
-    }
-
-    for mapping in maps {}
-
-    let min: usize = 0;
-
-    Ok((input, min))
+// merge overlapping and adjacent ranges into a sorted list, dropping empty ones
+pub fn coalesce(mut ranges: Vec<HalfOpenRange>) -> Vec<HalfOpenRange> {
+    ranges.retain(|r| !r.is_empty());
+    ranges.sort();
+    let mut coalesced: Vec<HalfOpenRange> = Vec::new();
+    for range in ranges {
+        match coalesced.last_mut() {
+            Some(top) if range.start <= top.end => top.end = top.end.max(range.end),
+            _ => coalesced.push(range),
+        }
+    }
+    coalesced
 }
 
-#[derive(Debug, Default, Eq)]
-pub struct InclusiveRange {
+// seeds come in `start length` pairs, an unpaired trailing number is ignored
+pub fn get_seeds(seeds: Vec<u32>) -> Vec<HalfOpenRange> {
+    let newseeds: Vec<HalfOpenRange> = seeds
+        .chunks_exact(2)
+        .map(|pair| HalfOpenRange::new(pair[0] as usize, pair[1] as usize))
+        .collect();
+    coalesce(newseeds)
+}
+
+// the ranges after every stage of the almanac, starting with the seeds
+pub fn stages(seeds: Vec<HalfOpenRange>, maps: &[Vec<HalfOpenRange>]) -> Vec<Vec<HalfOpenRange>> {
+    let mut stages: Vec<Vec<HalfOpenRange>> = vec![coalesce(seeds)];
+    for map in maps {
+        let next: Vec<HalfOpenRange> = coalesce(keep(stages.last().unwrap(), map));
+        stages.push(next);
+    }
+    stages
+}
+
+fn lowest_location(seeds: Vec<HalfOpenRange>, maps: &[Vec<HalfOpenRange>]) -> Option<usize> {
+    stages(seeds, maps).last().and_then(|locations| locations.first()).map(|r| r.start)
+}
+
+// every seed on its own
+fn lowest_seed_location(almanac: &Almanac) -> Result<usize, AocError> {
+    let seeds: Vec<HalfOpenRange> =
+        almanac.seeds.iter().map(|s| HalfOpenRange::new(*s as usize, 1)).collect();
+    lowest_location(seeds, &almanac.maps).ok_or(AocError::unsolvable("no seeds"))
+}
+
+// seeds as `start length` ranges
+fn lowest_range_location(almanac: &Almanac) -> Result<usize, AocError> {
+    lowest_location(get_seeds(almanac.seeds.clone()), &almanac.maps)
+        .ok_or(AocError::unsolvable("no seeds"))
+}
+
+// the parsed almanac, loaded from the cache when `--cache` is on
+fn almanac(input: &str) -> Result<Almanac, AocError> {
+    cache::parsed("day5", include_str!("day5.rs"), input, parse_almanac2)
+}
+
+pub fn part1(input: &str) -> Result<usize, AocError> {
+    lowest_seed_location(&almanac(input)?)
+}
+
+pub fn part2(input: &str) -> Result<usize, AocError> {
+    lowest_range_location(&almanac(input)?)
+}
+
+/// `start..end` with the end excluded, shifted by its projection when mapped.
+#[derive(Debug, Default, Clone, Copy, Eq, Serialize, Deserialize)]
+pub struct HalfOpenRange {
     start: usize,
     end: usize,
     projection: Projection,
 }
 
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 enum Projection {
     Add(usize),
     Subtract(usize),
+    #[default]
     None,
 }
 
-impl Default for Projection {
-    fn default() -> Self {
-        Projection::None
+impl HalfOpenRange {
+    pub fn new(start: usize, length: usize) -> Self {
+        Self { start, end: start + length, ..Default::default() }
     }
-}
 
-impl InclusiveRange {
-    fn new(start: usize, length: usize) -> Self {
-        Self { start, end: start + length, ..Default::default() }
+    // range covering start..end, end excluded
+    pub fn from_bounds(start: usize, end: usize) -> Self {
+        Self { start, end, ..Default::default() }
+    }
+
+    pub fn start(&self) -> usize {
+        self.start
+    }
+
+    pub fn len(&self) -> usize {
+        self.end - self.start
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.end == self.start
     }
 
     pub fn set_addition(&mut self, projection: usize) -> &mut Self {
@@ -166,24 +218,29 @@ impl InclusiveRange {
         self
     }
 
-    // check if there is an overlap between self and other
+    // check if there is an overlap between self and other, empty ranges overlap nothing
     pub fn overlaps(&self, other: &Self) -> bool {
-        self.start < other.end && other.start < self.end
+        !self.is_empty() && !other.is_empty() && self.start < other.end && other.start < self.end
     }
 
-    // return portion of self that doesn't overlap with other
+    // check if self contains other, an empty range is never contained
+    pub fn contains(&self, other: &Self) -> bool {
+        !other.is_empty() && self.start <= other.start && other.end <= self.end
+    }
+
+    // return portion of self below other
     pub fn lower_disjunction(&self, other: &Self) -> Option<Self> {
         if self.overlaps(other) && self.start < other.start {
-            Some(InclusiveRange::new(self.start, other.start))
+            Some(HalfOpenRange::from_bounds(self.start, other.start))
         } else {
             None
         }
     }
 
-    // return portion of self that doesn't overlap with other
+    // return portion of self above other
     pub fn upper_disjunction(&self, other: &Self) -> Option<Self> {
         if self.overlaps(other) && self.end > other.end {
-            Some(InclusiveRange::new(other.end + 1, self.end))
+            Some(HalfOpenRange::from_bounds(other.end, self.end))
         } else {
             None
         }
@@ -194,42 +251,44 @@ impl InclusiveRange {
         if self.overlaps(other) {
             let start: usize = if self.start >= other.start { self.start } else { other.start };
             let end: usize = if self.end <= other.end { self.end } else { other.end };
-            let mut conjunction: InclusiveRange = InclusiveRange::new(start, end);
-            let &mut conjunction = match self.projection {
-                Projection::Add(x) => conjunction.set_addition(x).project(),
-                Projection::Subtract(x) => conjunction.set_subtraction(x).project(),
-                Projection::None => &mut conjunction,
-            };
-            Some(conjunction)
+            let mut conjunction: HalfOpenRange = HalfOpenRange::from_bounds(start, end);
+            conjunction.projection = self.projection;
+            Some(*conjunction.project())
         } else {
             None
         }
     }
 }
 
-impl Ord for InclusiveRange {
+impl Ord for HalfOpenRange {
     fn cmp(&self, other: &Self) -> Ordering {
-        other.start.cmp(&self.start)
+        self.start.cmp(&other.start).then(self.end.cmp(&other.end))
     }
 }
 
-impl PartialOrd for InclusiveRange {
+impl PartialOrd for HalfOpenRange {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
         Some(self.cmp(other))
     }
 }
 
-impl PartialEq for InclusiveRange {
+impl PartialEq for HalfOpenRange {
     fn eq(&self, other: &Self) -> bool {
         self.start == other.start && self.end == other.end
     }
 }
 
+impl fmt::Display for HalfOpenRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}..{}", self.start, self.end)
+    }
+}
+
 impl CommandImpl for Day5 {
-    fn main(&self) -> Result<(), DynError> {
-        let string = read_to_string(&self.input).unwrap();
-        let (input, almanac) = parse_almanac2(&string).unwrap();
-        println!("min: {almanac}");
+    fn main(&self) -> Result<(), AocError> {
+        let string = read_input(&self.input)?;
+        println!("part 1: {}", part1(&string)?);
+        println!("part 2: {}", part2(&string)?);
 
         Ok(())
     }
@@ -238,12 +297,15 @@ impl CommandImpl for Day5 {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::utils::strategies::{bound, start_and_length};
+    use itertools::Itertools;
+    use proptest::prelude::*;
 
     #[test]
     fn test_parse_seeds() {
         let x: String = "seeds: 79 14 55 13".to_string();
         let input: &str = &x;
-        let (input, actual) = parse_seeds(input).unwrap();
+        let (_, actual) = parse_seeds(input).unwrap();
         let expected: Vec<u32> = vec![79, 14, 55, 13];
         assert_eq!(actual, expected);
     }
@@ -255,46 +317,123 @@ mod tests {
 "
         .to_string();
         let input: &str = &x;
-        let (input, actual) = parse_map(input).unwrap();
-        let expected: Vec<(usize, InclusiveRange)> =
-            vec![(0, InclusiveRange::new(15, 37)), (37, InclusiveRange::new(52, 2))];
+        let (_, actual) = parse_map(input).unwrap();
+        let expected: Vec<HalfOpenRange> =
+            vec![HalfOpenRange::new(15, 37), HalfOpenRange::new(52, 2)];
         assert_eq!(actual, expected);
+        assert_eq!(actual[0].conjunction(&actual[0]), Some(HalfOpenRange::new(0, 37)));
+    }
+
+    #[test]
+    fn test_get_seeds() {
+        assert!(get_seeds(vec![]).is_empty());
+        assert_eq!(get_seeds(vec![79, 14, 55]), vec![HalfOpenRange::new(79, 14)]);
+        assert!(parse_map("1 2").is_err());
     }
 
     #[test]
     fn test_range_contains() {
-        let this: InclusiveRange = InclusiveRange::new(5, 5);
-        let that: InclusiveRange = InclusiveRange::new(6, 3);
+        let this: HalfOpenRange = HalfOpenRange::new(5, 5);
+        let that: HalfOpenRange = HalfOpenRange::new(6, 3);
         assert!(this.contains(&that));
     }
 
     #[test]
     fn test_range_overlaps() {
-        let this: InclusiveRange = InclusiveRange::new(5, 5);
-        let that: InclusiveRange = InclusiveRange::new(9, 1);
+        let this: HalfOpenRange = HalfOpenRange::new(5, 5);
+        let that: HalfOpenRange = HalfOpenRange::new(9, 1);
         assert!(this.overlaps(&that));
     }
 
     #[test]
     fn test_range_overlap() {
-        let this: InclusiveRange = InclusiveRange::new(5, 5);
-        let temp: InclusiveRange = InclusiveRange::new(5, 5);
-        let that: InclusiveRange = this.overlap(&temp).unwrap();
+        let this: HalfOpenRange = HalfOpenRange::new(5, 5);
+        let temp: HalfOpenRange = HalfOpenRange::new(5, 5);
+        let that: HalfOpenRange = this.conjunction(&temp).unwrap();
         assert_eq!(this, that);
     }
 
     #[test]
     fn test_range_disjunction() {
-        let this: InclusiveRange = InclusiveRange::new(5, 5);
-        let temp: InclusiveRange = InclusiveRange::new(5, 5);
-        let that = this.disjunction(&temp);
-        assert!(that.is_none());
-
-        let this: InclusiveRange = InclusiveRange::new(4, 5);
-        let temp: InclusiveRange = InclusiveRange::new(5, 5);
-        let that = this.disjunction(&temp).unwrap();
-        let expected: InclusiveRange = InclusiveRange::new(4, 1);
-        assert_eq!(that.len(), 1);
-        assert_eq!(expected, that[0]);
+        let this: HalfOpenRange = HalfOpenRange::new(5, 5);
+        let temp: HalfOpenRange = HalfOpenRange::new(5, 5);
+        assert!(this.lower_disjunction(&temp).is_none());
+        assert!(this.upper_disjunction(&temp).is_none());
+
+        let this: HalfOpenRange = HalfOpenRange::new(4, 5);
+        let temp: HalfOpenRange = HalfOpenRange::new(5, 5);
+        let expected: HalfOpenRange = HalfOpenRange::new(4, 1);
+        assert_eq!(this.lower_disjunction(&temp), Some(expected));
+        assert!(this.upper_disjunction(&temp).is_none());
+    }
+
+    #[test]
+    fn test_almanac_stages() {
+        let input: &str = include_str!("../../../examples/day5/almanac.txt");
+        let almanac = parse_almanac2(input).unwrap();
+        let stages: Vec<Vec<HalfOpenRange>> = stages(get_seeds(almanac.seeds), &almanac.maps);
+        let rendered: String = stages.iter().map(|stage| stage.iter().join(" ") + "\n").collect();
+        crate::assert_snapshot!("day5_stages", rendered);
+    }
+
+    fn range() -> impl Strategy<Value = HalfOpenRange> {
+        start_and_length().prop_map(|(start, length)| HalfOpenRange::new(start, length))
+    }
+
+    // a map line `destination source length`
+    fn map_range() -> impl Strategy<Value = HalfOpenRange> {
+        (bound(), range()).prop_map(|(destination, mut range)| {
+            if destination >= range.start {
+                range.set_addition(destination - range.start);
+            } else {
+                range.set_subtraction(range.start - destination);
+            }
+            range
+        })
+    }
+
+    fn total_len(ranges: &[HalfOpenRange]) -> usize {
+        ranges.iter().map(|r| r.len()).sum()
+    }
+
+    proptest! {
+        #[test]
+        fn prop_conjunction_and_disjunctions_partition(this in range(), other in range()) {
+            let mut pieces: Vec<HalfOpenRange> = other.conjunction(&this).into_iter().collect();
+            pieces.extend(this.lower_disjunction(&other));
+            pieces.extend(this.upper_disjunction(&other));
+            if !this.overlaps(&other) {
+                prop_assert!(pieces.is_empty());
+                pieces.push(this);
+            }
+            pieces.sort();
+            prop_assert_eq!(total_len(&pieces), this.len());
+            prop_assert_eq!(pieces[0].start, this.start);
+            for pair in pieces.windows(2) {
+                prop_assert_eq!(pair[0].end, pair[1].start);
+            }
+        }
+
+        #[test]
+        fn prop_contains_implies_overlaps(this in range(), other in range()) {
+            prop_assert!(!this.contains(&other) || this.overlaps(&other));
+        }
+
+        #[test]
+        fn prop_coalesce_is_idempotent(ranges in prop::collection::vec(range(), 0..16)) {
+            let once: Vec<HalfOpenRange> = coalesce(ranges);
+            for pair in once.windows(2) {
+                prop_assert!(pair[0].end < pair[1].start);
+            }
+            prop_assert_eq!(coalesce(once.clone()), once);
+        }
+
+        #[test]
+        fn prop_keep_preserves_length(
+            ranges in prop::collection::vec(range(), 0..8),
+            map in prop::collection::vec(map_range(), 0..8),
+        ) {
+            prop_assert_eq!(total_len(&keep(&ranges, &map)), total_len(&ranges));
+        }
     }
 }
@@ -2,228 +2,140 @@ use std::path::PathBuf;
 
 use clap::Parser;
 
-use super::{CommandImpl, DynError};
-
-use std::fs::read_to_string;
+use super::CommandImpl;
+use crate::error::AocError;
 
 use std::cmp::Ordering;
 
-use intersection::hash_set;
+use crate::utils::{
+    line::FromLine,
+    parse::{lines, numbers},
+    read_input, sections,
+};
 use nom::{
-    branch::alt,
-    bytes::complete::{tag, take_until},
-    character::complete::{alpha0, alphanumeric1, digit1, newline, space0, space1, u32},
-    multi::{many1, separated_list1},
-    sequence::{delimited, preceded, separated_pair},
+    bytes::complete::tag,
+    character::complete::{space1, u32},
+    sequence::{preceded, tuple},
     IResult,
 };
-use std::collections::HashMap;
 use std::collections::HashSet;
-use std::fs::File;
-use std::io::{self, prelude::*, BufReader};
 
 #[derive(Parser, Debug)]
-pub struct Day5 {
+pub struct Day5Part1 {
     #[clap(long, short)]
     input: PathBuf,
 }
 
-fn parse_game(input: &str) -> IResult<&str, u32> {
-    let (input, _) = tag("Card")(input)?;
-    let (input, _) = space1(input)?;
-    let (input, _) = digit1(input)?;
-    let (input, _) = tag(":")(input)?;
-    let (input, _) = space1(input)?;
-    let (input, winning_numbers) = separated_list1(space1, u32)(input)?;
-    let (input, _) = space1(input)?;
-    let (input, _) = tag("|")(input)?;
-    let (input, _) = space1(input)?;
-    let (input, numbers) = separated_list1(space1, u32)(input)?;
-    let numbers: HashSet<u32> = HashSet::from_iter(numbers.iter().cloned());
-    let winning_numbers: HashSet<u32> = HashSet::from_iter(winning_numbers.iter().cloned());
-    let score = hash_set::intersection(vec![winning_numbers, numbers]);
-    let nmatches: u32 = score.len() as u32;
-
-    Ok((input, nmatches))
+#[derive(Debug, FromLine)]
+#[line("Card {id}: {winning: [u32; ' ']} | {have: [u32; ' ']}")]
+pub struct Card {
+    pub id: u32,
+    pub winning: HashSet<u32>,
+    pub have: HashSet<u32>,
+}
+
+impl Card {
+    // how many of the numbers we have are winning ones
+    pub fn matches(&self) -> usize {
+        self.winning.intersection(&self.have).count()
+    }
 }
 
 fn parse_range(input: &str) -> IResult<&str, Vec<u32>> {
-    let (input, soil_map) = separated_list1(space1, u32)(input)?;
-    Ok((input, soil_map))
+    let (input, (destination, source, length)) =
+        tuple((u32, preceded(space1, u32), preceded(space1, u32)))(input)?;
+    Ok((input, vec![destination, source, length]))
 }
 
-fn parse_map(input: &str) -> IResult<&str, Vec<Vec<u32>>> {
-    let (input, soil_maps) = separated_list1(newline, parse_range)(input)?;
-    Ok((input, soil_maps))
+pub fn parse_map(input: &str) -> IResult<&str, Vec<Vec<u32>>> {
+    lines(parse_range)(input)
 }
 
-fn parse_seeds(input: &str) -> IResult<&str, Vec<u32>> {
-    let (input, _) = tag("seeds:")(input)?;
-    let (input, _) = space1(input)?;
-    let (input, seeds) = separated_list1(space1, u32)(input)?;
-    Ok((input, seeds))
+pub fn parse_seeds(input: &str) -> IResult<&str, Vec<u32>> {
+    preceded(tag("seeds:"), numbers)(input)
 }
 
-pub fn keep(source: &Vec<InclusiveRange>, mymap: (usize,Vec<InclusiveRange>)) -> Vec<InclusiveRange> {
+pub fn keep(source: &HashSet<u32>, mymap: Vec<Vec<u32>>) -> HashSet<u32> {
     let mut destination: HashSet<u32> = HashSet::new();
     let mut source_found: HashSet<u32> = HashSet::new();
 
     for m in mymap {
-        let dest_start: u32 = m[0];
-        let source_start: u32 = m[1]; 
+        let dest_start: u64 = m[0] as u64;
+        let source_start: u32 = m[1];
         for s in source.iter() {
             if *s >= source_start && *s - source_start < m[2] {
                 let i: u32 = *s - source_start;
-                let dest_no: u32 = dest_start + i as u32;
-                let source_no: u32 = source_start + i as u32;
-                destination.insert(dest_no);
-                source_found.insert(source_no);
+                // destinations past u32::MAX cannot be looked up by a later map
+                if let Ok(dest_no) = u32::try_from(dest_start + i as u64) {
+                    destination.insert(dest_no);
+                }
+                source_found.insert(*s);
             }
         }
     }
 
-        for s in source.iter() {
-            if source_found.contains(s) {
-            } else {
-                destination.insert(*s);
-            }
+    for s in source.iter() {
+        if !source_found.contains(s) {
+            destination.insert(*s);
         }
+    }
     destination
 }
 
-fn parse_almanac(input: &str) -> IResult<&str, u32> {
-    let (input, seeds) = parse_seeds(input)?;
-    let seeds: HashSet<u32> = HashSet::from_iter(seeds.iter().cloned());
-
-    let (input, _) = newline(input)?;
-    let (input, _) = newline(input)?;
-    let (input, _) = tag("seed-to-soil map:")(input)?;
-    let (input, _) = newline(input)?;
-    let (input, seed_to_soil) = parse_map(input)?;
-    let (input, _) = newline(input)?;
-    let (input, _) = newline(input)?;
-    let numbers: HashSet<u32> = keep(&seeds,seed_to_soil);
-
-    let (input, _) = tag("soil-to-fertilizer map:")(input)?;
-    let (input, _) = newline(input)?;
-    let (input, soil_to_fertilizer) = parse_map(input)?;
-    let (input, _) = newline(input)?;
-    let (input, _) = newline(input)?;
-    let numbers: HashSet<u32> = keep(&numbers,soil_to_fertilizer);
-
-    let (input, _) = tag("fertilizer-to-water map:")(input)?;
-    let (input, _) = newline(input)?;
-    let (input, fertilizer_to_water) = parse_map(input)?;
-    let (input, _) = newline(input)?;
-    let (input, _) = newline(input)?;
-    let numbers: HashSet<u32> = keep(&numbers,fertilizer_to_water);
-
-    let seed_to_soil: HashMap<u32,u32> = HashMap::new();
-
-    let (input, _) = tag("water-to-light map:")(input)?;
-    let (input, _) = newline(input)?;
-    let (input, water_to_light) = parse_map(input)?;
-    let (input, _) = newline(input)?;
-    let (input, _) = newline(input)?;
-    let numbers: HashSet<u32> = keep(&numbers,water_to_light);
-
-    let (input, _) = tag("light-to-temperature map:")(input)?;
-    let (input, _) = newline(input)?;
-    let (input, light_to_temp) = parse_map(input)?;
-    let (input, _) = newline(input)?;
-    let (input, _) = newline(input)?;
-    let numbers: HashSet<u32> = keep(&numbers,light_to_temp);
-
-    let (input, _) = tag("temperature-to-humidity map:")(input)?;
-    let (input, _) = newline(input)?;
-    let (input, temp_to_humidity) = parse_map(input)?;
-    let (input, _) = newline(input)?;
-    let (input, _) = newline(input)?;
-    let numbers: HashSet<u32> = keep(&numbers,temp_to_humidity);
-
-    let (input, _) = tag("humidity-to-location map:")(input)?;
-    let (input, _) = newline(input)?;
-    let (input, humidity_to_location) = parse_map(input)?;
-    let numbers: HashSet<u32> = keep(&numbers,humidity_to_location);
-    let min = numbers.iter().min().unwrap();
-
-    Ok((input, *min))
+// the almanac's maps, in the order a seed goes through them
+const MAPS: [&str; 7] = [
+    "seed-to-soil",
+    "soil-to-fertilizer",
+    "fertilizer-to-water",
+    "water-to-light",
+    "light-to-temperature",
+    "temperature-to-humidity",
+    "humidity-to-location",
+];
+
+// send the seeds through every map of the almanac and find the lowest location
+fn lowest_location(
+    input: &str,
+    get_seeds: impl Fn(Vec<u32>) -> HashSet<u32>,
+) -> Result<u32, AocError> {
+    let sections = sections(input);
+    let Some((seeds, maps)) = sections.split_first() else {
+        return Err(AocError::parse("empty almanac"));
+    };
+    if seeds.header != Some("seeds") {
+        return Err(AocError::parse("the almanac does not start with seeds:"));
+    }
+    let headers: Vec<Option<&str>> = maps.iter().map(|map| map.header).collect();
+    if headers != MAPS.map(Some) {
+        return Err(AocError::parse(format!("expected the maps {MAPS:?}, got {headers:?}")));
+    }
+
+    let mut numbers: HashSet<u32> = get_seeds(seeds.parse(numbers)?.concat());
+    for map in maps {
+        numbers = keep(&numbers, map.parse(parse_range)?);
+    }
+    numbers.iter().min().copied().ok_or_else(|| AocError::parse("the almanac has no seeds"))
 }
 
+pub fn parse_almanac(input: &str) -> Result<u32, AocError> {
+    lowest_location(input, |seeds| seeds.into_iter().collect())
+}
+
+// seeds come in `start length` pairs, an unpaired trailing number is ignored
 pub fn get_seeds(seeds: Vec<u32>) -> HashSet<u32> {
-    let mut i = 0;
     let mut newseeds: HashSet<u32> = HashSet::new();
-    while i < seeds.len() - 1 {
-        let length: usize = seeds[i+1] as usize;
-        for j in 0..length {
-            let seed: u32 = seeds[i] + j as u32;
-            newseeds.insert(seed);
-        }
-        i += 2;
+    for pair in seeds.chunks_exact(2) {
+        let end: u32 = pair[0].saturating_add(pair[1]);
+        newseeds.extend(pair[0]..end);
     }
     newseeds
 }
 
-fn parse_almanac2(input: &str) -> IResult<&str, u32> {
-    let (input, seeds) = parse_seeds(input)?;
-    let seeds: HashSet<u32> = get_seeds(seeds); 
-
-    let (input, _) = newline(input)?;
-    let (input, _) = newline(input)?;
-    let (input, _) = tag("seed-to-soil map:")(input)?;
-    let (input, _) = newline(input)?;
-    let (input, seed_to_soil) = parse_map(input)?;
-    let (input, _) = newline(input)?;
-    let (input, _) = newline(input)?;
-    let numbers: HashSet<u32> = keep(&seeds,seed_to_soil);
-
-    let (input, _) = tag("soil-to-fertilizer map:")(input)?;
-    let (input, _) = newline(input)?;
-    let (input, soil_to_fertilizer) = parse_map(input)?;
-    let (input, _) = newline(input)?;
-    let (input, _) = newline(input)?;
-    let numbers: HashSet<u32> = keep(&numbers,soil_to_fertilizer);
-
-    let (input, _) = tag("fertilizer-to-water map:")(input)?;
-    let (input, _) = newline(input)?;
-    let (input, fertilizer_to_water) = parse_map(input)?;
-    let (input, _) = newline(input)?;
-    let (input, _) = newline(input)?;
-    let numbers: HashSet<u32> = keep(&numbers,fertilizer_to_water);
-
-    let seed_to_soil: HashMap<u32,u32> = HashMap::new();
-
-    let (input, _) = tag("water-to-light map:")(input)?;
-    let (input, _) = newline(input)?;
-    let (input, water_to_light) = parse_map(input)?;
-    let (input, _) = newline(input)?;
-    let (input, _) = newline(input)?;
-    let numbers: HashSet<u32> = keep(&numbers,water_to_light);
-
-    let (input, _) = tag("light-to-temperature map:")(input)?;
-    let (input, _) = newline(input)?;
-    let (input, light_to_temp) = parse_map(input)?;
-    let (input, _) = newline(input)?;
-    let (input, _) = newline(input)?;
-    let numbers: HashSet<u32> = keep(&numbers,light_to_temp);
-
-    let (input, _) = tag("temperature-to-humidity map:")(input)?;
-    let (input, _) = newline(input)?;
-    let (input, temp_to_humidity) = parse_map(input)?;
-    let (input, _) = newline(input)?;
-    let (input, _) = newline(input)?;
-    let numbers: HashSet<u32> = keep(&numbers,temp_to_humidity);
-
-    let (input, _) = tag("humidity-to-location map:")(input)?;
-    let (input, _) = newline(input)?;
-    let (input, humidity_to_location) = parse_map(input)?;
-    let numbers: HashSet<u32> = keep(&numbers,humidity_to_location);
-    let min = numbers.iter().min().unwrap();
-
-    Ok((input, *min))
+pub fn parse_almanac2(input: &str) -> Result<u32, AocError> {
+    lowest_location(input, get_seeds)
 }
 
-#[derive(Debug,Default,Eq)]
+#[derive(Debug, Default, Clone, Copy, Eq)]
 pub struct InclusiveRange {
     start: usize,
     length: usize,
@@ -231,72 +143,84 @@ pub struct InclusiveRange {
 
 impl InclusiveRange {
     pub fn new(start: usize, length: usize) -> Self {
-        InclusiveRange {
-            start: start,
-            length: length,
-        }
+        InclusiveRange { start, length }
+    }
+
+    pub fn len(&self) -> usize {
+        self.length
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.length == 0
+    }
+
+    // one past the last element, so empty ranges need no special casing
+    fn limit(&self) -> usize {
+        self.start + self.length
     }
 
     // check if there is an overlap between self and other
     pub fn overlaps(&self, other: &Self) -> bool {
-        let end_of_self: usize = self.start + self.length - 1;
-        let end_of_other: usize = other.start + other.length - 1;
-        end_of_self >= other.start && end_of_other >= self.start
+        !self.is_empty()
+            && !other.is_empty()
+            && self.start < other.limit()
+            && other.start < self.limit()
     }
 
-    // check if self contains other 
+    // check if self contains other, an empty range is never contained
     pub fn contains(&self, other: &Self) -> bool {
-        let end_of_self: usize = self.start + self.length - 1;
-        let end_of_other: usize = other.start + other.length - 1;
-        self.start <= other.start && end_of_other <= end_of_self 
+        !other.is_empty() && self.start <= other.start && other.limit() <= self.limit()
     }
 
-    pub fn end(&self) -> usize {
-        self.start + self.length - 1
+    // last element of the range, None for an empty range
+    pub fn end(&self) -> Option<usize> {
+        if self.is_empty() {
+            None
+        } else {
+            Some(self.limit() - 1)
+        }
     }
 
-    // return portion of self that doesn't overlap with other
+    // return portion of self that doesn't overlap with other, None if nothing is left
     pub fn disjunction(&self, other: &Self) -> Option<Vec<Self>> {
-        if self.contains(other) && other.contains(self) {
-            return None;
-        }
-        let mut nonoverlap: Vec<InclusiveRange> = Vec::new(); 
-        if self.start < other.start {
-            let newrange: InclusiveRange = InclusiveRange::new(self.start, other.start - self.start);
-            nonoverlap.push(newrange);
+        let mut nonoverlap: Vec<InclusiveRange> = Vec::new();
+        if !self.overlaps(other) {
+            nonoverlap.push(*self);
+        } else {
+            if self.start < other.start {
+                let newrange: InclusiveRange =
+                    InclusiveRange::new(self.start, other.start - self.start);
+                nonoverlap.push(newrange);
+            }
+            if self.limit() > other.limit() {
+                let newrange: InclusiveRange =
+                    InclusiveRange::new(other.limit(), self.limit() - other.limit());
+                nonoverlap.push(newrange);
+            }
         }
-        if self.end() > other.end() {
-            let newrange: InclusiveRange = InclusiveRange::new(other.end() + 1, self.end());
-            nonoverlap.push(newrange);
+        nonoverlap.retain(|r| !r.is_empty());
+        if nonoverlap.is_empty() {
+            None
+        } else {
+            Some(nonoverlap)
         }
-        Some(nonoverlap)
     }
 
     // return portion of self that overlaps with other
     pub fn overlap(&self, other: &Self) -> Option<Self> {
         if !self.overlaps(other) {
-            return None 
+            return None;
         }
 
-        let end_of_self: usize = self.start + self.length - 1;
-        let end_of_other: usize = other.start + other.length - 1;
-        let start_of_overlap = if self.start >= other.start {
-            self.start
-        } else {
-            other.start
-        };
-        let length_of_overlap = if end_of_self <= end_of_other {
-            end_of_self - start_of_overlap + 1 
-        } else {
-            end_of_other - start_of_overlap + 1 
-        };
+        let start_of_overlap = self.start.max(other.start);
+        let length_of_overlap = self.limit().min(other.limit()) - start_of_overlap;
         Some(InclusiveRange::new(start_of_overlap, length_of_overlap))
     }
 }
 
 impl Ord for InclusiveRange {
     fn cmp(&self, other: &Self) -> Ordering {
-        self.start.cmp(&other.start)
+        self.start.cmp(&other.start).then(self.length.cmp(&other.length))
     }
 }
 
@@ -312,12 +236,21 @@ impl PartialEq for InclusiveRange {
     }
 }
 
-impl CommandImpl for Day5 {
-    fn main(&self) -> Result<(), DynError> {
-        let string = read_to_string(&self.input).unwrap();
-        let (input, almanac) = parse_almanac2(&string).unwrap();
-        println!("min: {almanac}");
+pub fn part1(input: &str) -> Result<usize, AocError> {
+    let min = parse_almanac(input)?;
+    Ok(min as usize)
+}
+
+pub fn part2(input: &str) -> Result<usize, AocError> {
+    let min = parse_almanac2(input)?;
+    Ok(min as usize)
+}
 
+impl CommandImpl for Day5Part1 {
+    fn main(&self) -> Result<(), AocError> {
+        let string = read_input(&self.input)?;
+        println!("part 1: {}", part1(&string)?);
+        println!("part 2: {}", part2(&string)?);
 
         Ok(())
     }
@@ -326,13 +259,33 @@ impl CommandImpl for Day5 {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::utils::strategies::start_and_length;
+    use proptest::prelude::*;
+
+    #[test]
+    fn test_parse_card() {
+        let card: Card = "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53".parse().unwrap();
+        assert_eq!(card.id, 1);
+        assert_eq!(card.matches(), 4);
+        let error = "Card 1: 41 48 | 83 x".parse::<Card>().unwrap_err();
+        assert_eq!(error.column, 20);
+    }
+
+    #[test]
+    fn test_no_seeds_is_an_error() {
+        let almanac: &str = include_str!("../../../examples/day5part1/almanac.txt");
+        // a lone seed has no length, so part two has no ranges to send through the maps
+        let unpaired: String = almanac.replacen("seeds: 79 14 55 13", "seeds: 79", 1);
+        assert_eq!(part1(&unpaired).unwrap(), 82);
+        assert!(matches!(part2(&unpaired), Err(AocError::Parse { .. })));
+    }
 
     #[test]
     fn test_parse_seeds() {
         let x: String = "seeds: 79 14 55 13".to_string();
         let input: &str = &x;
-        let (input, actual) = parse_seeds(input).unwrap();
-        let expected: Vec<u32> = vec![79,14, 55, 13]; 
+        let (_, actual) = parse_seeds(input).unwrap();
+        let expected: Vec<u32> = vec![79, 14, 55, 13];
         assert_eq!(actual, expected);
     }
 
@@ -341,47 +294,111 @@ mod tests {
         let x: String = "0 15 37
 37 52 2
 39 0 15
-".to_string();
+"
+        .to_string();
         let input: &str = &x;
-        let (input, actual) = parse_map(input).unwrap();
-        let expected: Vec<Vec<u32>> = vec![vec![0,15,37], vec![37,52,2], vec![39,0,15]]; 
+        let (_, actual) = parse_map(input).unwrap();
+        let expected: Vec<Vec<u32>> = vec![vec![0, 15, 37], vec![37, 52, 2], vec![39, 0, 15]];
         assert_eq!(actual, expected);
     }
 
     #[test]
     fn test_range_contains() {
-        let this: InclusiveRange = InclusiveRange::new(5,5); 
-        let that: InclusiveRange = InclusiveRange::new(6,3); 
+        let this: InclusiveRange = InclusiveRange::new(5, 5);
+        let that: InclusiveRange = InclusiveRange::new(6, 3);
         assert!(this.contains(&that));
     }
 
     #[test]
     fn test_range_overlaps() {
-        let this: InclusiveRange = InclusiveRange::new(5,5); 
-        let that: InclusiveRange = InclusiveRange::new(9,1); 
+        let this: InclusiveRange = InclusiveRange::new(5, 5);
+        let that: InclusiveRange = InclusiveRange::new(9, 1);
         assert!(this.overlaps(&that));
     }
 
     #[test]
     fn test_range_overlap() {
-        let this: InclusiveRange = InclusiveRange::new(5,5); 
-        let temp: InclusiveRange = InclusiveRange::new(5,5); 
-        let that: InclusiveRange = this.overlap(&temp).unwrap(); 
-        assert_eq!(this,that);
+        let this: InclusiveRange = InclusiveRange::new(5, 5);
+        let temp: InclusiveRange = InclusiveRange::new(5, 5);
+        let that: InclusiveRange = this.overlap(&temp).unwrap();
+        assert_eq!(this, that);
     }
 
     #[test]
     fn test_range_disjunction() {
-        let this: InclusiveRange = InclusiveRange::new(5,5); 
-        let temp: InclusiveRange = InclusiveRange::new(5,5); 
-        let that = this.disjunction(&temp); 
+        let this: InclusiveRange = InclusiveRange::new(5, 5);
+        let temp: InclusiveRange = InclusiveRange::new(5, 5);
+        let that = this.disjunction(&temp);
         assert!(that.is_none());
 
-        let this: InclusiveRange = InclusiveRange::new(4,5); 
-        let temp: InclusiveRange = InclusiveRange::new(5,5); 
-        let that = this.disjunction(&temp).unwrap(); 
-        let expected: InclusiveRange = InclusiveRange::new(4,1); 
-        assert_eq!(that.len(),1);
-        assert_eq!(expected,that[0]);
+        let this: InclusiveRange = InclusiveRange::new(4, 5);
+        let temp: InclusiveRange = InclusiveRange::new(5, 5);
+        let that = this.disjunction(&temp).unwrap();
+        let expected: InclusiveRange = InclusiveRange::new(4, 1);
+        assert_eq!(that.len(), 1);
+        assert_eq!(expected, that[0]);
+    }
+
+    #[test]
+    fn test_range_end() {
+        assert_eq!(InclusiveRange::new(5, 5).end(), Some(9));
+        assert_eq!(InclusiveRange::new(0, 0).end(), None);
+    }
+
+    fn range() -> impl Strategy<Value = InclusiveRange> {
+        start_and_length().prop_map(|(start, length)| InclusiveRange::new(start, length))
+    }
+
+    proptest! {
+        #[test]
+        fn prop_overlap_and_disjunction_partition(this in range(), other in range()) {
+            let mut pieces: Vec<InclusiveRange> = this.disjunction(&other).unwrap_or_default();
+            pieces.extend(this.overlap(&other));
+            pieces.sort();
+            prop_assert!(pieces.iter().all(|p| !p.is_empty()));
+            prop_assert_eq!(pieces.iter().map(|p| p.len()).sum::<usize>(), this.len());
+            if let Some(first) = pieces.first() {
+                prop_assert_eq!(first.start, this.start);
+            }
+            for pair in pieces.windows(2) {
+                prop_assert_eq!(pair[0].limit(), pair[1].start);
+            }
+        }
+
+        #[test]
+        fn prop_end_is_the_last_element(this in range()) {
+            match this.end() {
+                None => prop_assert!(this.is_empty()),
+                Some(end) => {
+                    prop_assert_eq!(end + 1, this.limit());
+                    prop_assert!(this.contains(&InclusiveRange::new(end, 1)));
+                }
+            }
+        }
+
+        #[test]
+        fn prop_empty_ranges_have_no_end_or_overlap(
+            (start, _) in start_and_length(),
+            other in range(),
+        ) {
+            let empty = InclusiveRange::new(start, 0);
+            prop_assert_eq!(empty.end(), None);
+            prop_assert!(!empty.overlaps(&other) && !other.overlaps(&empty));
+            prop_assert!(!other.contains(&empty));
+            prop_assert_eq!(empty.overlap(&other), None);
+            prop_assert_eq!(empty.disjunction(&other), None);
+        }
+
+        #[test]
+        fn prop_contains_implies_overlaps(this in range(), other in range()) {
+            prop_assert!(!this.contains(&other) || this.overlaps(&other));
+        }
+
+        #[test]
+        fn prop_overlap_is_contained_in_both(this in range(), other in range()) {
+            if let Some(overlap) = this.overlap(&other) {
+                prop_assert!(this.contains(&overlap) && other.contains(&overlap));
+            }
+        }
     }
 }
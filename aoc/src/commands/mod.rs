@@ -1,12 +1,23 @@
+pub mod day1;
 pub mod day10;
+pub mod day5;
+pub mod day5part1;
 
-use std::error::Error;
-
+use clap::Parser;
 use enum_dispatch::enum_dispatch;
 
-pub type DynError = Box<dyn Error + 'static>;
+use crate::error::AocError;
 
 #[enum_dispatch]
 pub trait CommandImpl {
-    fn main(&self) -> Result<(), DynError>;
+    fn main(&self) -> Result<(), AocError>;
+}
+
+#[enum_dispatch(CommandImpl)]
+#[derive(Parser, Debug)]
+pub enum SubCommand {
+    Day1(day1::Day1),
+    Day10(day10::Day10),
+    Day5(day5::Day5),
+    Day5Part1(day5part1::Day5Part1),
 }
@@ -0,0 +1 @@
+pub use aoc_common::error::{AocError, Location};
@@ -0,0 +1,8 @@
+// lets `::aoc::` paths from `#[derive(FromLine)]` resolve inside this crate too
+extern crate self as aoc;
+
+pub mod commands;
+pub mod error;
+#[cfg(test)]
+mod snapshot;
+pub mod utils;
@@ -1,24 +1,67 @@
-pub mod commands;
-pub mod utils;
+use std::{
+    any::Any,
+    panic::{self, AssertUnwindSafe},
+    path::PathBuf,
+    process::ExitCode,
+    sync::mpsc::{self, RecvTimeoutError},
+    thread,
+    time::Duration,
+};
 
-use commands::*;
-use enum_dispatch::enum_dispatch;
+use aoc::{commands::*, error::AocError, utils::cache};
 
 use clap::Parser;
 
 #[derive(Parser, Debug)]
 struct Opts {
+    /// Give up on the day after this many seconds
+    #[clap(long, global = true)]
+    timeout: Option<u64>,
+    /// Reuse parsed inputs, from `--cache=DIR` or `.cache` by default
+    #[clap(long, global = true, value_name = "DIR", num_args = 0..=1, require_equals = true, default_missing_value = ".cache")]
+    cache: Option<PathBuf>,
     #[clap(subcommand)]
     subcommand: SubCommand,
 }
 
-#[enum_dispatch(CommandImpl)]
-#[derive(Parser, Debug)]
-enum SubCommand {
-    Day10(day10::Day10),
+fn run(opts: Opts) -> Result<(), AocError> {
+    if let Some(dir) = opts.cache {
+        cache::enable(dir);
+    }
+    let Some(seconds) = opts.timeout else {
+        // a panic is an error like any other, so scripts see its exit code and not Rust's 101
+        return panic::catch_unwind(AssertUnwindSafe(|| opts.subcommand.main()))
+            .unwrap_or_else(|payload| Err(AocError::Panicked(panic_message(payload.as_ref()))));
+    };
+    let limit = Duration::from_secs(seconds);
+    let (sender, receiver) = mpsc::channel();
+    // the solver thread is abandoned on timeout, exiting the process stops it
+    let solver = thread::spawn(move || sender.send(opts.subcommand.main()));
+    match receiver.recv_timeout(limit) {
+        Ok(result) => result,
+        Err(RecvTimeoutError::Timeout) => Err(AocError::Timeout(limit)),
+        // the sender is only dropped without sending when the solver panicked
+        Err(RecvTimeoutError::Disconnected) => match solver.join() {
+            Err(payload) => Err(AocError::Panicked(panic_message(payload.as_ref()))),
+            Ok(_) => Err(AocError::Panicked("the solver stopped without an answer".into())),
+        },
+    }
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    match (payload.downcast_ref::<&str>(), payload.downcast_ref::<String>()) {
+        (Some(msg), _) => (*msg).to_owned(),
+        (_, Some(msg)) => msg.clone(),
+        _ => "no message".to_owned(),
+    }
 }
-fn main() -> Result<(), DynError> {
-    let opts = Opts::parse();
 
-    opts.subcommand.main()
+fn main() -> ExitCode {
+    match run(Opts::parse()) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("error: {e}");
+            ExitCode::from(e.exit_code())
+        }
+    }
 }
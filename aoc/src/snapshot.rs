@@ -0,0 +1,54 @@
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+};
+
+use aoc_common::diff::diff_lines;
+
+/// Set to `1` to overwrite recorded snapshots with the current output.
+pub const UPDATE_VAR: &str = "UPDATE_SNAPSHOTS";
+
+pub fn snapshot_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("..").join("snapshots")
+}
+
+/// Compare `actual` against `snapshots/<name>.snap`, recording it on first run.
+///
+/// # Panics
+///
+/// Panics with a line diff when the recorded snapshot differs from `actual`.
+pub fn check_snapshot(name: &str, actual: &str) {
+    let path = snapshot_dir().join(format!("{name}.snap"));
+    let update = env::var(UPDATE_VAR).is_ok_and(|v| v == "1");
+    match fs::read_to_string(&path) {
+        Ok(expected) if expected == actual => {}
+        Ok(expected) if !update => {
+            panic!(
+                "snapshot {name} does not match {}\n{}\nrerun with {UPDATE_VAR}=1 to accept",
+                path.display(),
+                diff_lines(&expected, actual)
+            );
+        }
+        _ => {
+            fs::create_dir_all(snapshot_dir()).expect("Failed to create snapshot directory");
+            fs::write(&path, actual).expect("Failed to write snapshot");
+            println!("recorded snapshot {}", path.display());
+        }
+    }
+}
+
+/// Snapshot a value through its `Display` impl.
+#[macro_export]
+macro_rules! assert_snapshot {
+    ($name:expr, $value:expr) => {
+        $crate::snapshot::check_snapshot($name, &format!("{}", $value))
+    };
+}
+
+/// Snapshot a value through its pretty-printed `Debug` impl.
+#[macro_export]
+macro_rules! assert_debug_snapshot {
+    ($name:expr, $value:expr) => {
+        $crate::snapshot::check_snapshot($name, &format!("{:#?}", $value))
+    };
+}
@@ -0,0 +1,232 @@
+//! A byte oriented path for large inputs: the file is memory mapped with the `mmap` feature
+//! and parsed in place, so results borrow from it instead of copying.
+
+use std::{fs::File, io::Read, ops::Deref, path::Path};
+
+use crate::error::{AocError, Location};
+
+use super::{grid::Grid, not_found, point::Point};
+
+enum Contents {
+    #[cfg(feature = "mmap")]
+    Mapped(memmap2::Mmap),
+    Owned(Vec<u8>),
+}
+
+/// The raw bytes of a puzzle input, without a UTF-8 BOM.
+pub struct Input {
+    contents: Contents,
+}
+
+impl Input {
+    /// Map `path` into memory with the `mmap` feature, otherwise read it.
+    #[allow(clippy::missing_errors_doc)]
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Input, AocError> {
+        let mut file = File::open(&path).map_err(not_found(path.as_ref()))?;
+        #[cfg(feature = "mmap")]
+        if file.metadata()?.len() > 0 {
+            // SAFETY: inputs are not written to while a day runs
+            let map = unsafe { memmap2::Mmap::map(&file)? };
+            return Ok(Input { contents: Contents::Mapped(map) });
+        }
+        let mut bytes = vec![];
+        file.read_to_end(&mut bytes)?;
+        Ok(Input { contents: Contents::Owned(bytes) })
+    }
+}
+
+impl Deref for Input {
+    type Target = [u8];
+
+    // a single arm without the `mmap` feature
+    #[allow(clippy::infallible_destructuring_match)]
+    fn deref(&self) -> &[u8] {
+        let bytes: &[u8] = match &self.contents {
+            #[cfg(feature = "mmap")]
+            Contents::Mapped(map) => map,
+            Contents::Owned(bytes) => bytes,
+        };
+        bytes.strip_prefix(b"\xef\xbb\xbf").unwrap_or(bytes)
+    }
+}
+
+/// The lines of `input` without their `\n` or `\r\n`.
+pub fn lines(input: &[u8]) -> impl Iterator<Item = &[u8]> {
+    let input = input.strip_suffix(b"\n").unwrap_or(input);
+    input
+        .split(|b| *b == b'\n')
+        .filter(move |_| !input.is_empty())
+        .map(|line| line.strip_suffix(b"\r").unwrap_or(line))
+}
+
+/// An unsigned integer at the start of `input` and what follows it, `None` without a digit
+/// or on overflow.
+pub fn uint(input: &[u8]) -> Option<(u64, &[u8])> {
+    let digits = input.iter().take_while(|b| b.is_ascii_digit()).count();
+    if digits == 0 {
+        return None;
+    }
+    let mut value: u64 = 0;
+    for b in &input[..digits] {
+        value = value.checked_mul(10)?.checked_add(u64::from(b - b'0'))?;
+    }
+    Some((value, &input[digits..]))
+}
+
+/// Every run of digits in `input`, skipping whatever separates them.
+pub fn uints(mut input: &[u8]) -> impl Iterator<Item = Option<u64>> + '_ {
+    std::iter::from_fn(move || {
+        let start = input.iter().position(u8::is_ascii_digit)?;
+        match uint(&input[start..]) {
+            Some((value, rest)) => {
+                input = rest;
+                Some(Some(value))
+            }
+            None => {
+                input = &[];
+                Some(None)
+            }
+        }
+    })
+}
+
+/// A rectangular block of bytes, borrowed from the input it was found in.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ByteGrid<'a> {
+    rows: Vec<&'a [u8]>,
+    width: usize,
+}
+
+impl<'a> ByteGrid<'a> {
+    /// Check that every line of `input` is as wide as the first.
+    ///
+    /// Lines are read the way [`super::normalize`] leaves them: trailing whitespace and `\r`
+    /// are dropped, as are blank lines at the end.
+    #[allow(clippy::missing_errors_doc)]
+    pub fn new(input: &'a [u8]) -> Result<Self, AocError> {
+        let mut rows: Vec<&[u8]> = lines(input).map(<[u8]>::trim_ascii_end).collect();
+        while rows.last().is_some_and(|row| row.is_empty()) {
+            rows.pop();
+        }
+        let width = rows.first().map_or(0, |row| row.len());
+        if width == 0 {
+            return Err(AocError::parse_at(Location::line(1), "empty grid"));
+        }
+        if let Some(i) = rows.iter().position(|row| row.len() != width) {
+            let msg = format!("expected {width} tiles, got {}", rows[i].len());
+            return Err(AocError::parse_at(Location::line(i + 1), msg));
+        }
+        if Point::from_index(width, rows.len()).is_none() {
+            return Err(AocError::parse(format!("a {width}x{} grid is too large", rows.len())));
+        }
+        Ok(ByteGrid { rows, width })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.rows.len()
+    }
+
+    pub fn row(&self, row: usize) -> Option<&'a [u8]> {
+        self.rows.get(row).copied()
+    }
+
+    pub fn get(&self, row: usize, col: usize) -> Option<u8> {
+        (col < self.width).then(|| self.row(row).map(|r| r[col])).flatten()
+    }
+
+    /// Check every tile in place, reporting the first rejected one at its line and column.
+    #[allow(clippy::missing_errors_doc)]
+    pub fn check<T, E>(&self, mut tile: impl FnMut(u8) -> Result<T, E>) -> Result<(), AocError>
+    where
+        E: std::fmt::Display,
+    {
+        for (r, row) in self.rows.iter().enumerate() {
+            for (c, b) in row.iter().enumerate() {
+                if let Err(e) = tile(*b) {
+                    let location = Location { line: r + 1, column: Some(c + 1) };
+                    return Err(AocError::parse_at(location, e.to_string()));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Convert every tile, reporting the first rejected one at its line and column.
+    #[allow(clippy::missing_errors_doc)]
+    pub fn map<T, E>(&self, mut tile: impl FnMut(u8) -> Result<T, E>) -> Result<Grid<T>, AocError>
+    where
+        E: std::fmt::Display,
+    {
+        let rows = self
+            .rows
+            .iter()
+            .enumerate()
+            .map(|(r, row)| {
+                row.iter()
+                    .enumerate()
+                    .map(|(c, b)| {
+                        tile(*b).map_err(|e| {
+                            let location = Location { line: r + 1, column: Some(c + 1) };
+                            AocError::parse_at(location, e.to_string())
+                        })
+                    })
+                    .collect()
+            })
+            .collect::<Result<_, _>>()?;
+        Grid::from_rows(rows)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lines_and_uints() {
+        assert_eq!(lines(b"a\r\nbc\n\nd\n").collect::<Vec<_>>(), [&b"a"[..], b"bc", b"", b"d"]);
+        assert_eq!(lines(b"").count(), 0);
+        assert_eq!(uint(b"123 4"), Some((123, &b" 4"[..])));
+        assert_eq!(uint(b"x"), None);
+        assert_eq!(uint(b"99999999999999999999"), None);
+        let numbers: Vec<_> = uints(b"seeds: 79 14\n55  13").collect();
+        assert_eq!(numbers, [Some(79), Some(14), Some(55), Some(13)]);
+    }
+
+    #[test]
+    fn test_byte_grid() {
+        let grid = ByteGrid::new(b"S-7\r\n|.|\r\nL-J\r\n").unwrap();
+        assert_eq!((grid.width(), grid.height()), (3, 3));
+        assert_eq!(grid.row(1), Some(&b"|.|"[..]));
+        assert_eq!(grid.get(2, 2), Some(b'J'));
+        assert_eq!(grid.get(0, 3), None);
+        assert_eq!(grid.get(3, 0), None);
+
+        assert_eq!(ByteGrid::new(b"S-7\n|.\n").unwrap_err().line(), Some(2));
+        assert_eq!(ByteGrid::new(b"\n").unwrap_err().line(), Some(1));
+        // normalized like `read_input`: mixed endings, trailing spaces and blank lines at the end
+        let grid = ByteGrid::new(b"S-7 \r\n|.|\nL-J\t\n\n  \n").unwrap();
+        assert_eq!((grid.width(), grid.height()), (3, 3));
+        assert_eq!(grid.row(0), Some(&b"S-7"[..]));
+        assert!(grid.check(|b| if b == b'.' { Err("a dot") } else { Ok(b) }).is_err());
+        let error = ByteGrid::new(b"S-7\n|x|\n").unwrap().map(|b| match b {
+            b'x' => Err("unexpected x"),
+            b => Ok(b),
+        });
+        assert!(error.unwrap_err().to_string().starts_with("parse error at line 2, column 2"));
+    }
+
+    #[test]
+    fn test_input_strips_bom() {
+        let path = std::env::temp_dir().join(format!("aoc-bytes-{}", std::process::id()));
+        std::fs::write(&path, "\u{feff}1\n2\n").unwrap();
+        let input = Input::open(&path).unwrap();
+        assert_eq!(&*input, b"1\n2\n");
+        drop(input);
+        std::fs::remove_file(&path).unwrap();
+        assert!(matches!(Input::open(&path), Err(AocError::InputNotFound(_))));
+    }
+}
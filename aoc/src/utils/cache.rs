@@ -0,0 +1,141 @@
+//! `--cache`: parsed inputs stored on disk, keyed by the input and the parser that read it.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    sync::OnceLock,
+};
+
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::error::AocError;
+
+static CACHE_DIR: OnceLock<PathBuf> = OnceLock::new();
+
+// the shared parsers every day builds on and the modules they reach, a change to any of
+// them invalidates every entry
+const SHARED_SOURCES: [&str; 6] = [
+    include_str!("mod.rs"),
+    include_str!("parse.rs"),
+    include_str!("grid.rs"),
+    include_str!("point.rs"),
+    include_str!("bytes.rs"),
+    include_str!("line.rs"),
+];
+
+/// Cache parsed inputs in `dir` for the rest of the process.
+pub fn enable(dir: PathBuf) {
+    let _ = CACHE_DIR.set(dir);
+}
+
+/// FNV-1a over the parts, each followed by a 0xff separator; stable across toolchains
+/// unlike `DefaultHasher`.
+pub fn fingerprint(parts: &[&[u8]]) -> u64 {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for part in parts {
+        for b in part.iter().chain(&[0xff]) {
+            hash ^= u64::from(*b);
+            hash = hash.wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+    hash
+}
+
+/// Parse `input` with `parse`, or load the result of an earlier run when `--cache` is on.
+///
+/// `source` is the day's own source, `include_str!` of its file, so editing the day
+/// or the shared parsers makes it parse again.
+#[allow(clippy::missing_errors_doc)]
+pub fn parsed<T, F>(day: &str, source: &str, input: &str, parse: F) -> Result<T, AocError>
+where
+    T: Serialize + DeserializeOwned,
+    F: FnOnce(&str) -> Result<T, AocError>,
+{
+    match CACHE_DIR.get() {
+        Some(dir) => parsed_in(dir, day, source, input, parse),
+        None => parse(input),
+    }
+}
+
+fn parsed_in<T, F>(
+    dir: &Path,
+    day: &str,
+    source: &str,
+    input: &str,
+    parse: F,
+) -> Result<T, AocError>
+where
+    T: Serialize + DeserializeOwned,
+    F: FnOnce(&str) -> Result<T, AocError>,
+{
+    let mut parser = vec![source.as_bytes()];
+    parser.extend(SHARED_SOURCES.map(str::as_bytes));
+    let parser = format!("{:016x}", fingerprint(&parser));
+    let key = format!("{day}-{:016x}-{parser}.bin", fingerprint(&[input.as_bytes()]));
+    let path = dir.join(&key);
+    // an unreadable entry, say from an older serde layout, is parsed again and replaced
+    if let Some(value) = fs::read(&path).ok().and_then(|bytes| bincode::deserialize(&bytes).ok()) {
+        return Ok(value);
+    }
+
+    let value = parse(input)?;
+    let bytes = bincode::serialize(&value).map_err(|e| AocError::Io(std::io::Error::other(e)))?;
+    fs::create_dir_all(dir)?;
+    // drop the day's entries from older parsers so the directory does not grow with every
+    // edit, other inputs read by the current parser stay cached
+    for entry in fs::read_dir(dir)? {
+        let name = entry?.file_name();
+        let name = name.to_string_lossy();
+        let stale = !name.ends_with(&format!("-{parser}.bin"));
+        if name.starts_with(&format!("{day}-")) && name.ends_with(".bin") && stale {
+            fs::remove_file(dir.join(name.as_ref()))?;
+        }
+    }
+    let partial = dir.join(format!("{key}.partial"));
+    fs::write(&partial, bytes)?;
+    fs::rename(partial, path)?;
+    Ok(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+
+    #[test]
+    fn test_fingerprint() {
+        assert_eq!(fingerprint(&[b""]), fingerprint(&[b""]));
+        assert_ne!(fingerprint(&[b"ab", b"c"]), fingerprint(&[b"a", b"bc"]));
+        // pinned, a different hash would silently orphan every cached entry
+        assert_eq!(fingerprint(&[b"a"]), 0x089b_c907_b544_c769);
+    }
+
+    #[test]
+    fn test_parsed_in() {
+        let dir = std::env::temp_dir().join(format!("aoc-cache-{}", std::process::id()));
+        let calls = Cell::new(0);
+        let parse = |input: &str| -> Result<Vec<u32>, AocError> {
+            calls.set(calls.get() + 1);
+            input
+                .split(',')
+                .map(|n| n.parse().map_err(|_| AocError::parse("not a number")))
+                .collect()
+        };
+
+        assert_eq!(parsed_in(&dir, "day7", "v1", "1,2", parse).unwrap(), [1, 2]);
+        assert_eq!(parsed_in(&dir, "day7", "v1", "1,2", parse).unwrap(), [1, 2]);
+        assert_eq!(calls.get(), 1);
+        assert_eq!(parsed_in(&dir, "day7", "v1", "3", parse).unwrap(), [3]);
+        assert_eq!(calls.get(), 2);
+        // both inputs stay cached under the same parser
+        assert_eq!(fs::read_dir(&dir).unwrap().count(), 2);
+        assert_eq!(parsed_in(&dir, "day7", "v1", "1,2", parse).unwrap(), [1, 2]);
+        assert_eq!(calls.get(), 2);
+        assert_eq!(parsed_in(&dir, "day7", "v2", "3", parse).unwrap(), [3]);
+        assert_eq!(calls.get(), 3);
+        assert_eq!(fs::read_dir(&dir).unwrap().count(), 1);
+
+        assert!(parsed_in(&dir, "day7", "v2", "x", parse).is_err());
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,194 @@
+//! Cycles in iterated functions: `state, f(state), f(f(state)), ...` must repeat eventually
+//! when there are finitely many states, and then the billionth one is cheap to find.
+//!
+//! [`floyd`] and [`brent`] keep two states at a time and only need `Eq`; [`detect`]
+//! remembers every state it has seen, which costs memory but also returns them.
+
+use std::{collections::HashMap, hash::Hash};
+
+/// Where the repetition begins and how long it is: for `n >= start` the state after `n`
+/// steps is the same as after `n + length`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cycle {
+    pub start: usize,
+    pub length: usize,
+}
+
+impl Cycle {
+    /// The first step, before the cycle has repeated, that lands on the same state as
+    /// step `n`.
+    pub fn reduce(&self, n: usize) -> usize {
+        if n < self.start {
+            n
+        } else {
+            self.start + (n - self.start) % self.length
+        }
+    }
+}
+
+// the state after `n` steps from `state`
+fn iterate<S>(mut state: S, f: &mut impl FnMut(&S) -> S, n: usize) -> S {
+    for _ in 0..n {
+        state = f(&state);
+    }
+    state
+}
+
+/// Floyd's tortoise and hare. Does not return if the states never repeat.
+pub fn floyd<S: Eq>(initial: S, mut f: impl FnMut(&S) -> S) -> Cycle {
+    // the hare runs twice as fast, they meet somewhere inside the cycle
+    let mut tortoise = f(&initial);
+    let mut hare = f(&tortoise);
+    while tortoise != hare {
+        tortoise = f(&tortoise);
+        let next = f(&hare);
+        hare = f(&next);
+    }
+
+    // the meeting point is a whole number of cycles ahead, so walking both at the same
+    // speed, one from the initial state, they meet again where the cycle starts
+    let mut start = 0;
+    let mut tortoise = initial;
+    while tortoise != hare {
+        tortoise = f(&tortoise);
+        hare = f(&hare);
+        start += 1;
+    }
+
+    let mut length = 1;
+    let mut hare = f(&tortoise);
+    while tortoise != hare {
+        hare = f(&hare);
+        length += 1;
+    }
+    Cycle { start, length }
+}
+
+/// Brent's algorithm, usually fewer calls to `f` than [`floyd`]. Does not return if the
+/// states never repeat.
+pub fn brent<S: Clone + Eq>(initial: S, mut f: impl FnMut(&S) -> S) -> Cycle {
+    // the tortoise teleports to the hare at every power of two until the hare catches it
+    let mut power = 1;
+    let mut length = 1;
+    let mut tortoise = initial.clone();
+    let mut hare = f(&initial);
+    while tortoise != hare {
+        if power == length {
+            tortoise = hare.clone();
+            power *= 2;
+            length = 0;
+        }
+        hare = f(&hare);
+        length += 1;
+    }
+
+    // with the hare a full cycle ahead, step both until they meet at the start
+    let mut start = 0;
+    let mut tortoise = initial.clone();
+    let mut hare = iterate(initial, &mut f, length);
+    while tortoise != hare {
+        tortoise = f(&tortoise);
+        hare = f(&hare);
+        start += 1;
+    }
+    Cycle { start, length }
+}
+
+/// Every state up to the first repeat and the cycle they form.
+#[derive(Debug, Clone)]
+pub struct Seen<S> {
+    /// The states in order, `states[0]` is the initial one
+    pub states: Vec<S>,
+    pub cycle: Cycle,
+}
+
+impl<S> Seen<S> {
+    /// The state after `n` steps, however large `n` is.
+    pub fn state_after(&self, n: usize) -> &S {
+        &self.states[self.cycle.reduce(n)]
+    }
+}
+
+/// Step from `initial` until a state comes back. Does not return if the states never
+/// repeat.
+pub fn detect<S: Clone + Eq + Hash>(initial: S, mut f: impl FnMut(&S) -> S) -> Seen<S> {
+    let mut index = HashMap::new();
+    let mut states = vec![];
+    let mut state = initial;
+    loop {
+        if let Some(&start) = index.get(&state) {
+            let length = states.len() - start;
+            return Seen { states, cycle: Cycle { start, length } };
+        }
+        index.insert(state.clone(), states.len());
+        let next = f(&state);
+        states.push(std::mem::replace(&mut state, next));
+    }
+}
+
+/// The state after `n` steps from `initial`, skipping whole cycles once one shows up.
+pub fn nth<S: Clone + Eq + Hash>(initial: S, mut f: impl FnMut(&S) -> S, n: usize) -> S {
+    let mut index = HashMap::new();
+    let mut state = initial;
+    for step in 0..n {
+        if let Some(&start) = index.get(&state) {
+            let cycle = Cycle { start, length: step - start };
+            // `state` is the one after `step` steps, so only the remainder is left to go
+            let remaining = cycle.reduce(n) - cycle.reduce(step);
+            return iterate(state, &mut f, remaining);
+        }
+        index.insert(state.clone(), step);
+        state = f(&state);
+    }
+    state
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::grid::Grid;
+
+    // 0 -> 1 -> 2 -> 3 -> 4 -> 2 -> ...
+    fn lasso(x: &u32) -> u32 {
+        if *x < 4 {
+            x + 1
+        } else {
+            2
+        }
+    }
+
+    #[test]
+    fn test_cycle_finders_agree() {
+        let expected = Cycle { start: 2, length: 3 };
+        assert_eq!(floyd(0, lasso), expected);
+        assert_eq!(brent(0, lasso), expected);
+        assert_eq!(detect(0, lasso).cycle, expected);
+        assert_eq!(floyd(3, lasso), Cycle { start: 0, length: 3 });
+
+        let square = |x: &u64| (x * x + 1) % 255;
+        for initial in [0, 3, 100] {
+            let seen = detect(initial, square);
+            assert_eq!(floyd(initial, square), seen.cycle);
+            assert_eq!(brent(initial, square), seen.cycle);
+        }
+    }
+
+    #[test]
+    fn test_extrapolation() {
+        let seen = detect(0, lasso);
+        assert_eq!(seen.states, [0, 1, 2, 3, 4]);
+        assert_eq!(seen.cycle.reduce(1), 1);
+        assert_eq!(seen.cycle.reduce(5), 2);
+        assert_eq!(*seen.state_after(1_000_000_000), 4);
+        for n in [0, 1, 4, 5, 1_000_000_000] {
+            assert_eq!(nth(0, lasso, n), *seen.state_after(n));
+        }
+
+        // whole grids repeat too, a quarter turn comes back after four
+        let grid: Grid<char> = "ab\ncd\n".parse().unwrap();
+        let turns = detect(grid.clone(), Grid::rotate);
+        assert_eq!(turns.cycle, Cycle { start: 0, length: 4 });
+        assert_eq!(turns.state_after(1_000_000_001).to_string(), "ca\ndb\n");
+        assert_eq!(nth(grid, Grid::rotate, 1_000_000_001).to_string(), "ca\ndb\n");
+    }
+}
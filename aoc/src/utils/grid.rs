@@ -0,0 +1,378 @@
+//! A rectangular grid stored row by row, indexed with [`Point`]s, and the [`Cells`] lookups
+//! it shares with [`SparseGrid`](super::sparse::SparseGrid) and the endless [`Tiled`] view.
+
+use std::{
+    fmt,
+    ops::{Index, IndexMut},
+    str::FromStr,
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    error::{AocError, Location},
+    utils::{
+        parse::{complete, rows},
+        point::Point,
+    },
+};
+
+/// Cells looked up by [`Point`], whatever holds them.
+pub trait Cells {
+    type Cell;
+
+    /// The cell at `point`, `None` where there is none.
+    fn get(&self, point: Point) -> Option<&Self::Cell>;
+
+    /// The top left and bottom right points that hold a cell, `None` when there are none or
+    /// they never end.
+    fn bounds(&self) -> Option<(Point, Point)>;
+
+    fn contains(&self, point: Point) -> bool {
+        self.get(point).is_some()
+    }
+
+    /// The points north, east, south and west of `point` that hold a cell.
+    fn neighbors4(&self, point: Point) -> impl Iterator<Item = Point> + '_ {
+        point.neighbors4().into_iter().filter(|next| self.contains(*next))
+    }
+
+    /// [`Cells::neighbors4`] followed by the diagonal neighbours, clockwise from north east.
+    fn neighbors8(&self, point: Point) -> impl Iterator<Item = Point> + '_ {
+        point.neighbors8().into_iter().filter(|next| self.contains(*next))
+    }
+
+    /// Every point within [`Cells::bounds`] that holds a cell, row by row, none when they never
+    /// end.
+    fn points(&self) -> impl Iterator<Item = Point> + '_ {
+        let (min, max) = self.bounds().unwrap_or((Point::ORIGIN, Point::new(-1, -1)));
+        (min.y()..=max.y())
+            .flat_map(move |y| (min.x()..=max.x()).map(move |x| Point::new(x, y)))
+            .filter(|point| self.contains(*point))
+    }
+
+    /// The cells from `min` to `max`, one row per line with `.` where there is none.
+    fn render(&self, min: Point, max: Point) -> String
+    where
+        Self: Sized,
+        Self::Cell: fmt::Display,
+    {
+        let mut out = String::new();
+        let _ = write_cells(&mut out, self, min, max);
+        out
+    }
+}
+
+// shared by every `Display`, so all of them print the same way
+pub(crate) fn write_cells<C>(
+    out: &mut impl fmt::Write,
+    cells: &C,
+    min: Point,
+    max: Point,
+) -> fmt::Result
+where
+    C: Cells,
+    C::Cell: fmt::Display,
+{
+    for y in min.y()..=max.y() {
+        for x in min.x()..=max.x() {
+            match cells.get(Point::new(x, y)) {
+                Some(cell) => write!(out, "{cell}")?,
+                None => write!(out, ".")?,
+            }
+        }
+        writeln!(out)?;
+    }
+    Ok(())
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    width: usize,
+    height: usize,
+}
+
+impl<T> Grid<T> {
+    /// A `width` x `height` grid with every cell set to `fill`.
+    pub fn new(width: usize, height: usize, fill: T) -> Self
+    where
+        T: Clone,
+    {
+        Grid { cells: vec![fill; width * height], width, height }
+    }
+
+    /// Check that every row is as wide as the first, reporting the first one that is not.
+    #[allow(clippy::missing_errors_doc)]
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Result<Self, AocError> {
+        let width = rows.first().map_or(0, Vec::len);
+        let height = rows.len();
+        if let Some(i) = rows.iter().position(|row| row.len() != width) {
+            let msg = format!("expected {width} tiles, got {}", rows[i].len());
+            return Err(AocError::parse_at(Location::line(i + 1), msg));
+        }
+        if Point::from_index(width, height).is_none() {
+            return Err(AocError::parse(format!("a {width}x{height} grid is too large")));
+        }
+        Ok(Grid { cells: rows.into_iter().flatten().collect(), width, height })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    // where `point` is stored, `None` off the grid
+    fn index(&self, point: Point) -> Option<usize> {
+        let (x, y) = point.to_index()?;
+        (x < self.width && y < self.height).then_some(y * self.width + x)
+    }
+
+    pub fn contains(&self, point: Point) -> bool {
+        self.index(point).is_some()
+    }
+
+    pub fn get(&self, point: Point) -> Option<&T> {
+        self.index(point).map(|i| &self.cells[i])
+    }
+
+    pub fn get_mut(&mut self, point: Point) -> Option<&mut T> {
+        self.index(point).map(|i| &mut self.cells[i])
+    }
+
+    /// Every point, row by row.
+    pub fn points(&self) -> impl Iterator<Item = Point> {
+        // from_rows made sure both dimensions fit in an i32
+        let (width, height) = (self.width as i32, self.height as i32);
+        (0..height).flat_map(move |y| (0..width).map(move |x| Point::new(x, y)))
+    }
+
+    /// Every cell with its point, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+        self.points().zip(&self.cells)
+    }
+
+    pub fn row(&self, row: usize) -> Option<&[T]> {
+        (row < self.height).then(|| &self.cells[row * self.width..][..self.width])
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // `chunks` panics on 0, an empty grid has no rows either way
+        self.cells.chunks(self.width.max(1))
+    }
+
+    pub fn column(&self, col: usize) -> impl DoubleEndedIterator<Item = &T> {
+        let cells = if col < self.width { &self.cells[col..] } else { &[] };
+        cells.iter().step_by(self.width.max(1))
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl DoubleEndedIterator<Item = &T>> {
+        (0..self.width).map(|col| self.column(col))
+    }
+
+    /// The point of the first cell, row by row, matching `predicate`.
+    pub fn find(&self, mut predicate: impl FnMut(&T) -> bool) -> Option<Point> {
+        self.iter().find(|(_, cell)| predicate(cell)).map(|(point, _)| point)
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid { cells: self.cells.iter().map(f).collect(), width: self.width, height: self.height }
+    }
+
+    /// Rows become columns.
+    pub fn transpose(&self) -> Self
+    where
+        T: Clone,
+    {
+        let cells = self.columns().flatten().cloned().collect();
+        Grid { cells, width: self.height, height: self.width }
+    }
+
+    /// A quarter turn clockwise.
+    pub fn rotate(&self) -> Self
+    where
+        T: Clone,
+    {
+        let cells = self.columns().flat_map(|column| column.rev()).cloned().collect();
+        Grid { cells, width: self.height, height: self.width }
+    }
+
+    /// The grid repeated endlessly in every direction.
+    pub fn tiled(&self) -> Tiled<'_, T> {
+        Tiled { grid: self }
+    }
+}
+
+impl<T> Cells for Grid<T> {
+    type Cell = T;
+
+    fn get(&self, point: Point) -> Option<&T> {
+        Grid::get(self, point)
+    }
+
+    fn bounds(&self) -> Option<(Point, Point)> {
+        let max = Point::from_index(self.width.checked_sub(1)?, self.height.checked_sub(1)?)?;
+        Some((Point::ORIGIN, max))
+    }
+}
+
+impl<T> Index<Point> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, point: Point) -> &T {
+        match self.get(point) {
+            Some(cell) => cell,
+            None => panic!("{point} is outside the {}x{} grid", self.width, self.height),
+        }
+    }
+}
+
+impl<T> IndexMut<Point> for Grid<T> {
+    fn index_mut(&mut self, point: Point) -> &mut T {
+        let (width, height) = (self.width, self.height);
+        match self.get_mut(point) {
+            Some(cell) => cell,
+            None => panic!("{point} is outside the {width}x{height} grid"),
+        }
+    }
+}
+
+/// Every row on its own line, like the input it was parsed from.
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.bounds() {
+            Some((min, max)) => write_cells(f, self, min, max),
+            None => Ok(()),
+        }
+    }
+}
+
+/// One cell per character, through `T::try_from`.
+impl<T: TryFrom<char>> FromStr for Grid<T> {
+    type Err = AocError;
+
+    fn from_str(input: &str) -> Result<Self, AocError> {
+        Grid::from_rows(complete(input, rows)?)
+    }
+}
+
+/// A [`Grid`] repeated endlessly, so every point has a cell unless the grid is empty.
+#[derive(Debug, Clone, Copy)]
+pub struct Tiled<'a, T> {
+    grid: &'a Grid<T>,
+}
+
+impl<'a, T> Tiled<'a, T> {
+    /// The point of the underlying grid that `point` lands on.
+    pub fn wrap(&self, point: Point) -> Point {
+        // from_rows made sure both dimensions fit in an i32
+        let (width, height) = (self.grid.width.max(1) as i32, self.grid.height.max(1) as i32);
+        Point::new(point.x().rem_euclid(width), point.y().rem_euclid(height))
+    }
+
+    /// Which copy of the grid `point` is in, the original one is at the origin.
+    pub fn tile(&self, point: Point) -> Point {
+        let (width, height) = (self.grid.width.max(1) as i32, self.grid.height.max(1) as i32);
+        Point::new(point.x().div_euclid(width), point.y().div_euclid(height))
+    }
+
+    pub fn get(&self, point: Point) -> Option<&'a T> {
+        self.grid.get(self.wrap(point))
+    }
+}
+
+impl<T> Cells for Tiled<'_, T> {
+    type Cell = T;
+
+    fn get(&self, point: Point) -> Option<&T> {
+        Tiled::get(self, point)
+    }
+
+    fn bounds(&self) -> Option<(Point, Point)> {
+        None
+    }
+}
+
+impl<T> Index<Point> for Tiled<'_, T> {
+    type Output = T;
+
+    fn index(&self, point: Point) -> &T {
+        &self.grid[self.wrap(point)]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn digits() -> Grid<u32> {
+        Grid::from_rows(vec![vec![1, 2, 3], vec![4, 5, 6]]).unwrap()
+    }
+
+    #[test]
+    fn test_access_and_neighbors() {
+        let mut grid = digits();
+        let p = Point::new;
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid.get(p(2, 1)), Some(&6));
+        assert_eq!(grid.get(p(0, 2)), None);
+        assert_eq!(grid.get(p(3, 0)), None);
+        assert_eq!(grid.get(p(-1, 0)), None);
+        *grid.get_mut(p(0, 0)).unwrap() = 9;
+        grid[p(0, 1)] += 1;
+        assert_eq!(grid.row(0), Some(&[9, 2, 3][..]));
+        assert_eq!(grid.row(1), Some(&[5, 5, 6][..]));
+
+        assert_eq!(grid.neighbors4(p(0, 0)).collect::<Vec<_>>(), [p(1, 0), p(0, 1)]);
+        assert_eq!(grid.neighbors4(p(1, 1)).collect::<Vec<_>>(), [p(1, 0), p(2, 1), p(0, 1)]);
+        assert_eq!(grid.neighbors8(p(0, 0)).collect::<Vec<_>>(), [p(1, 0), p(0, 1), p(1, 1)]);
+        assert_eq!(grid.neighbors8(p(1, 1)).count(), 5);
+        assert_eq!(grid.find(|d| *d == 5), Some(p(0, 1)));
+        assert_eq!(grid.find(|d| *d == 7), None);
+        assert_eq!(grid.points().last(), Some(p(2, 1)));
+    }
+
+    #[test]
+    fn test_rows_columns_and_turns() {
+        let grid = digits();
+        assert_eq!(grid.rows().collect::<Vec<_>>(), [[1, 2, 3], [4, 5, 6]]);
+        let columns: Vec<Vec<u32>> = grid.columns().map(|c| c.copied().collect()).collect();
+        assert_eq!(columns, [[1, 4], [2, 5], [3, 6]]);
+        assert_eq!(grid.column(3).count(), 0);
+
+        assert_eq!(grid.transpose().to_string(), "14\n25\n36\n");
+        assert_eq!(grid.rotate().to_string(), "41\n52\n63\n");
+        assert_eq!(grid.rotate().rotate().rotate().rotate(), grid);
+        assert_eq!(grid.transpose().transpose(), grid);
+        assert_eq!(grid.map(|d| d * 2).get(Point::new(2, 1)), Some(&12));
+    }
+
+    #[test]
+    fn test_tiled() {
+        let grid = digits();
+        let tiled = grid.tiled();
+        assert_eq!(tiled[Point::new(4, 3)], 5);
+        assert_eq!(tiled.get(Point::new(-1, -1)), Some(&6));
+        assert_eq!(tiled.wrap(Point::new(-4, 2)), Point::new(2, 0));
+        assert_eq!(tiled.tile(Point::new(-4, 2)), Point::new(-2, 1));
+        assert_eq!(tiled.neighbors8(Point::new(-10, 7)).count(), 8);
+        assert_eq!(tiled.bounds(), None);
+        assert_eq!(tiled.render(Point::new(-1, 0), Point::new(3, 1)), "31231\n64564\n");
+        let empty: Grid<u8> = Grid::from_rows(vec![]).unwrap();
+        assert_eq!(empty.tiled().get(Point::new(3, 3)), None);
+    }
+
+    #[test]
+    fn test_from_str() {
+        let grid: Grid<char> = "ab\ncd\n".parse().unwrap();
+        assert_eq!(grid.to_string(), "ab\ncd\n");
+        assert_eq!("ab\nc\n".parse::<Grid<char>>().unwrap_err().line(), Some(2));
+        assert_eq!(Grid::from_rows(vec![vec![1], vec![]]).unwrap_err().line(), Some(2));
+        let empty: Grid<u8> = Grid::from_rows(vec![]).unwrap();
+        assert_eq!(empty.rows().count(), 0);
+        assert_eq!(empty.points().count(), 0);
+    }
+}
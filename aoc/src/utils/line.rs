@@ -0,0 +1,162 @@
+//! Runtime side of `#[derive(FromLine)]`, matching a line against its pattern piece by piece.
+
+use std::{error::Error, fmt, iter::FromIterator, str::FromStr};
+
+pub use aoc_derive::FromLine;
+
+/// Why a line did not match its pattern, at a 1-based column.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LineError {
+    pub column: usize,
+    pub msg: String,
+}
+
+impl fmt::Display for LineError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "column {}: {}", self.column, self.msg)
+    }
+}
+
+impl Error for LineError {}
+
+/// A position in the line being matched.
+pub struct Cursor<'a> {
+    line: &'a str,
+    pos: usize,
+}
+
+impl<'a> Cursor<'a> {
+    pub fn new(line: &'a str) -> Self {
+        Cursor { line: line.trim_end_matches(['\r', '\n']), pos: 0 }
+    }
+
+    fn rest(&self) -> &'a str {
+        &self.line[self.pos..]
+    }
+
+    fn error(&self, at: usize, msg: String) -> LineError {
+        LineError { column: self.line[..at].chars().count() + 1, msg }
+    }
+
+    /// Match `text`, where a space matches any run of spaces.
+    pub fn literal(&mut self, text: &str) -> Result<(), LineError> {
+        for c in text.chars() {
+            let rest = self.rest();
+            if c == ' ' {
+                let trimmed = rest.trim_start_matches(' ');
+                if trimmed.len() == rest.len() {
+                    return Err(self.error(self.pos, format!("expected a space in {text:?}")));
+                }
+                self.pos += rest.len() - trimmed.len();
+            } else if rest.starts_with(c) {
+                self.pos += c.len_utf8();
+            } else {
+                return Err(self.error(self.pos, format!("expected {c:?} of {text:?}")));
+            }
+        }
+        Ok(())
+    }
+
+    // everything up to the text following the field, or to the end of the line
+    fn take(&mut self, until: Option<&str>) -> (usize, &'a str) {
+        let start = self.pos;
+        let rest = self.rest();
+        let end = match until.map(str::trim_start) {
+            None => rest.len(),
+            Some("") => rest.find(' ').unwrap_or(rest.len()),
+            Some(stop) => rest.find(stop).unwrap_or(rest.len()),
+        };
+        let value = rest[..end].trim_end_matches(' ');
+        self.pos += value.len();
+        (start, value)
+    }
+
+    /// Parse the field `name` from the text up to `until`.
+    pub fn field<T>(&mut self, name: &str, until: Option<&str>) -> Result<T, LineError>
+    where
+        T: FromStr,
+        <T as FromStr>::Err: fmt::Display,
+    {
+        let (start, value) = self.take(until);
+        value.parse().map_err(|e| self.error(start, format!("invalid {name} {value:?}: {e}")))
+    }
+
+    /// Parse the field `name` as `separator` separated `T`s up to `until`.
+    pub fn list<T, C>(
+        &mut self,
+        name: &str,
+        separator: char,
+        until: Option<&str>,
+    ) -> Result<C, LineError>
+    where
+        T: FromStr,
+        <T as FromStr>::Err: fmt::Display,
+        C: FromIterator<T>,
+    {
+        let (start, value) = self.take(until);
+        let mut offset = start;
+        let mut items = vec![];
+        for item in value.split(separator) {
+            if !item.is_empty() {
+                let parsed = item.parse().map_err(|e| {
+                    self.error(offset, format!("invalid {name} item {item:?}: {e}"))
+                })?;
+                items.push(parsed);
+            }
+            offset += item.len() + separator.len_utf8();
+        }
+        Ok(items.into_iter().collect())
+    }
+
+    /// Require the whole line to have been matched, allowing trailing spaces.
+    pub fn finish(&self) -> Result<(), LineError> {
+        let rest = self.rest();
+        if rest.trim_end().is_empty() {
+            Ok(())
+        } else {
+            Err(self.error(self.pos, format!("unexpected {rest:?}")))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[derive(Debug, PartialEq, FromLine)]
+    #[line("Card {id}: {winning: [u32; ' ']} | {have: [u32; ' ']}")]
+    struct Card {
+        id: u32,
+        winning: Vec<u32>,
+        have: HashSet<u32>,
+    }
+
+    #[derive(Debug, PartialEq, FromLine)]
+    #[line("{name} = ({left}, {right})")]
+    struct Node {
+        name: String,
+        left: String,
+        right: String,
+    }
+
+    #[test]
+    fn test_derive_from_line() {
+        let card: Card = "Card   3:  1 21 53 | 69 82 63  1".parse().unwrap();
+        assert_eq!(card.id, 3);
+        assert_eq!(card.winning, [1, 21, 53]);
+        assert_eq!(card.have, HashSet::from([69, 82, 63, 1]));
+
+        let node: Node = "AAA = (BBB, CCC)".parse().unwrap();
+        assert_eq!(node, Node { name: "AAA".into(), left: "BBB".into(), right: "CCC".into() });
+    }
+
+    #[test]
+    fn test_from_line_errors() {
+        let error = "Card 1: 1 x | 2".parse::<Card>().unwrap_err();
+        assert_eq!(error.column, 11);
+        assert_eq!("Game 1: 1 | 2".parse::<Card>().unwrap_err().column, 1);
+        assert_eq!("Card 1: 1 | 2 |".parse::<Card>().unwrap_err().column, 15);
+        assert_eq!("AAA = (BBB, CCC".parse::<Node>().unwrap_err().column, 16);
+    }
+}
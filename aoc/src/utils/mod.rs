@@ -0,0 +1,282 @@
+pub mod bytes;
+pub mod cache;
+pub mod cycle;
+pub mod grid;
+pub mod line;
+pub mod parse;
+pub mod point;
+pub mod search;
+pub mod sparse;
+#[cfg(test)]
+pub mod strategies;
+
+use crate::error::{AocError, Location};
+use line::LineError;
+use nom::IResult;
+use std::{
+    any::Any,
+    borrow::Cow,
+    error::Error,
+    fmt::{self, Debug},
+    fs::{self, File},
+    io::{self, BufRead, BufReader},
+    path::Path,
+    str::FromStr,
+};
+
+fn not_found(path: &Path) -> impl FnOnce(io::Error) -> AocError + '_ {
+    move |e| match e.kind() {
+        io::ErrorKind::NotFound => AocError::InputNotFound(path.to_owned()),
+        _ => AocError::Io(e),
+    }
+}
+
+pub use aoc_common::text::normalize;
+
+/// Read a puzzle input, telling a missing file apart from other io errors.
+///
+/// The input is [`normalize`]d, so days only ever see `\n` line endings.
+#[allow(clippy::missing_errors_doc)]
+pub fn read_input<P: AsRef<Path>>(path: P) -> Result<String, AocError> {
+    let input = fs::read_to_string(&path).map_err(not_found(path.as_ref()))?;
+    Ok(match normalize(&input) {
+        Cow::Borrowed(normalized) if normalized.len() == input.len() => input,
+        normalized => normalized.into_owned(),
+    })
+}
+
+/// Every malformed line of a file, as reported by [`slurp_all`].
+#[derive(Debug)]
+pub struct SlurpErrors(pub Vec<AocError>);
+
+impl fmt::Display for SlurpErrors {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, error) in self.0.iter().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            write!(f, "{error}")?;
+        }
+        Ok(())
+    }
+}
+
+impl Error for SlurpErrors {}
+
+/// Lazily parse a file line by line, numbering lines from 1.
+#[allow(clippy::missing_errors_doc)]
+pub fn slurp_lines<P, T>(path: P) -> Result<impl Iterator<Item = Result<T, AocError>>, AocError>
+where
+    P: AsRef<Path>,
+    T: FromStr,
+    <T as FromStr>::Err: Error + 'static,
+{
+    let reader = File::open(&path).map(BufReader::new).map_err(not_found(path.as_ref()))?;
+    Ok(reader.lines().enumerate().map(|(i, line)| {
+        let line = line?;
+        let line = if i == 0 { line.trim_start_matches('\u{feff}') } else { &line };
+        line.trim_end().parse::<T>().map_err(|e| {
+            // a `FromLine` error knows its column, anything else only the line
+            let (column, msg) = match (&e as &dyn Any).downcast_ref::<LineError>() {
+                Some(e) => (Some(e.column), e.msg.clone()),
+                None => (None, e.to_string()),
+            };
+            AocError::parse_at(Location { line: i + 1, column }, msg)
+        })
+    }))
+}
+
+#[allow(clippy::missing_errors_doc)]
+pub fn slurp_file<P, T>(path: P) -> Result<Vec<T>, AocError>
+where
+    P: AsRef<Path>,
+    T: FromStr,
+    <T as FromStr>::Err: Error + 'static,
+{
+    slurp_lines(path)?.collect()
+}
+
+/// Like [`slurp_file`] but reports every malformed line instead of just the first.
+#[allow(clippy::missing_errors_doc)]
+pub fn slurp_all<P, T>(path: P) -> Result<Vec<T>, SlurpErrors>
+where
+    P: AsRef<Path>,
+    T: FromStr,
+    <T as FromStr>::Err: Error + 'static,
+{
+    let lines = slurp_lines(path).map_err(|e| SlurpErrors(vec![e]))?;
+    let mut values = vec![];
+    let mut errors = vec![];
+    for line in lines {
+        match line {
+            Ok(value) => values.push(value),
+            Err(e) => errors.push(e),
+        }
+    }
+    if errors.is_empty() {
+        Ok(values)
+    } else {
+        Err(SlurpErrors(errors))
+    }
+}
+
+/// One blank line separated block of an input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Section<'a> {
+    /// `seeds` for `seeds: 79 14`, `seed-to-soil` for `seed-to-soil map:`
+    pub header: Option<&'a str>,
+    /// The body, starting with whatever followed the header on its line
+    pub lines: Vec<&'a str>,
+    numbers: Vec<usize>,
+}
+
+impl<'a> Section<'a> {
+    /// Parse every body line, which `parse_line` has to consume entirely.
+    pub fn parse<T, F>(&self, mut parse_line: F) -> Result<Vec<T>, AocError>
+    where
+        F: FnMut(&'a str) -> IResult<&'a str, T>,
+    {
+        self.lines
+            .iter()
+            .zip(&self.numbers)
+            .map(|(line, number)| match parse_line(line) {
+                Ok(("", value)) => Ok(value),
+                Ok((rest, _)) => {
+                    Err(AocError::parse_at(Location::line(*number), format!("unexpected {rest:?}")))
+                }
+                Err(e) => Err(AocError::parse_at(Location::line(*number), e.to_string())),
+            })
+            .collect()
+    }
+}
+
+// `seed-to-soil map: 1 2` -> (`seed-to-soil`, `1 2`), `None` for a line without a header
+fn split_header(line: &str) -> Option<(&str, &str)> {
+    let (name, rest) = line.split_once(':')?;
+    let name = name.trim();
+    let name = name.strip_suffix(" map").unwrap_or(name);
+    let valid = !name.is_empty()
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_' || c == ' ');
+    valid.then_some((name, rest.trim()))
+}
+
+/// Split an input into its blank line separated sections, tolerating CRLF and trailing newlines.
+pub fn sections(input: &str) -> Vec<Section<'_>> {
+    let mut sections: Vec<Section> = vec![];
+    let mut current: Option<Section> = None;
+    for (i, line) in input.lines().enumerate() {
+        let line = line.trim_end_matches('\r');
+        if line.trim().is_empty() {
+            sections.extend(current.take());
+            continue;
+        }
+        match current.as_mut() {
+            Some(section) => {
+                section.lines.push(line);
+                section.numbers.push(i + 1);
+            }
+            None => {
+                let mut section = Section { header: None, lines: vec![], numbers: vec![] };
+                match split_header(line) {
+                    Some((name, rest)) => {
+                        section.header = Some(name);
+                        if !rest.is_empty() {
+                            section.lines.push(rest);
+                            section.numbers.push(i + 1);
+                        }
+                    }
+                    None => {
+                        section.lines.push(line);
+                        section.numbers.push(i + 1);
+                    }
+                }
+                current = Some(section);
+            }
+        }
+    }
+    sections.extend(current);
+    sections
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write_temp(name: &str, contents: &str) -> std::path::PathBuf {
+        let path = std::env::temp_dir().join(format!("aoc-utils-{}-{name}", std::process::id()));
+        std::fs::write(&path, contents).unwrap();
+        path
+    }
+
+    #[test]
+    fn test_slurp_lines() {
+        let path = write_temp("lines", "1\n2\nx\n4\ny\n");
+        let mut lines = slurp_lines::<_, u32>(&path).unwrap();
+        assert_eq!(lines.next().unwrap().unwrap(), 1);
+        assert_eq!(lines.next().unwrap().unwrap(), 2);
+        assert_eq!(lines.next().unwrap().unwrap_err().line(), Some(3));
+        drop(lines);
+        assert_eq!(slurp_file::<_, u32>(&path).unwrap_err().line(), Some(3));
+
+        let errors = slurp_all::<_, u32>(&path).unwrap_err();
+        assert_eq!(errors.0.iter().map(AocError::line).collect::<Vec<_>>(), [Some(3), Some(5)]);
+        assert!(errors.to_string().starts_with("parse error at line 3: "));
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_sections() {
+        let input =
+            "seeds: 79 14\r\n\r\nseed-to-soil map:\r\n50 98 2\r\n52 50 48\r\n\r\n\r\n1\n2\n\n\n";
+        let sections = sections(input);
+        let headers: Vec<_> = sections.iter().map(|s| s.header).collect();
+        assert_eq!(headers, [Some("seeds"), Some("seed-to-soil"), None]);
+        assert_eq!(sections[0].lines, ["79 14"]);
+        assert_eq!(sections[1].lines, ["50 98 2", "52 50 48"]);
+        assert_eq!(sections[2].lines, ["1", "2"]);
+
+        let numbers = sections[2].parse(nom::character::complete::u32).unwrap();
+        assert_eq!(numbers, [1, 2]);
+        let error = sections[1].parse(nom::character::complete::u32).unwrap_err();
+        assert_eq!(error.to_string(), "parse error at line 4: unexpected \" 98 2\"");
+        assert!(super::sections("\n\n").is_empty());
+    }
+
+    #[derive(Debug, line::FromLine)]
+    #[line("{x},{y}")]
+    struct Point {
+        x: i32,
+        y: i32,
+    }
+
+    #[test]
+    fn test_slurp_from_line() {
+        let path = write_temp("from-line", "1,2\n-3,4\n5,x\n");
+        let error = slurp_file::<_, Point>(&path).unwrap_err();
+        assert!(matches!(
+            error,
+            AocError::Parse { location: Some(Location { line: 3, column: Some(3) }), .. }
+        ));
+        std::fs::write(&path, "1,2\n-3,4\n").unwrap();
+        let points = slurp_file::<_, Point>(&path).unwrap();
+        assert_eq!((points[1].x, points[1].y), (-3, 4));
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_normalize() {
+        let path = write_temp("normalize", "\u{feff}7 \r\n8\r\n");
+        assert_eq!(read_input(&path).unwrap(), "7\n8\n");
+        assert_eq!(slurp_file::<_, u32>(&path).unwrap(), [7, 8]);
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_slurp_missing_file() {
+        let path = std::env::temp_dir().join("aoc-utils-missing-file");
+        assert!(matches!(slurp_lines::<_, u32>(&path), Err(AocError::InputNotFound(_))));
+        assert!(matches!(slurp_file::<_, u32>(&path), Err(AocError::InputNotFound(_))));
+        assert_eq!(slurp_all::<_, u32>(&path).unwrap_err().0[0].exit_code(), 5);
+        assert!(matches!(read_input(&path), Err(AocError::InputNotFound(_))));
+    }
+}
@@ -0,0 +1,157 @@
+//! Nom combinators shared by the days; [`complete`] runs one over a whole input.
+
+use std::str::FromStr;
+
+use nom::{
+    bytes::complete::{tag, take_till1},
+    character::complete::{
+        char, digit1, line_ending, multispace0, none_of, one_of, space0, space1,
+    },
+    combinator::{all_consuming, map_res, opt, recognize, verify},
+    error::{Error, ErrorKind},
+    multi::{many1, separated_list1},
+    sequence::{delimited, pair, terminated, tuple},
+    IResult,
+};
+
+use crate::{error::AocError, utils::grid::Grid};
+
+/// Run `parser` over all of `input`, allowing only trailing whitespace to remain.
+pub fn complete<'a, O, F>(input: &'a str, parser: F) -> Result<O, AocError>
+where
+    F: FnMut(&'a str) -> IResult<&'a str, O>,
+{
+    let (_, output) = all_consuming(terminated(parser, multispace0))(input)
+        .map_err(|e| AocError::parse_in(input, e))?;
+    Ok(output)
+}
+
+/// An integer of any type, with an optional sign.
+pub fn number<T: FromStr>(input: &str) -> IResult<&str, T> {
+    map_res(recognize(pair(opt(one_of("+-")), digit1)), str::parse)(input)
+}
+
+/// Numbers separated by spaces or tabs, like `79 14 55 13` or `-2  4`.
+pub fn numbers<T: FromStr>(input: &str) -> IResult<&str, Vec<T>> {
+    let (input, _) = space0(input)?;
+    separated_list1(space1, number)(input)
+}
+
+/// One `parser` per line, tolerating CRLF.
+pub fn lines<'a, O, F>(parser: F) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<O>>
+where
+    F: FnMut(&'a str) -> IResult<&'a str, O>,
+{
+    separated_list1(line_ending, parser)
+}
+
+/// A `Card 12: body` record, giving the number with whatever `body` parses.
+pub fn labelled<'a, O, F>(
+    label: &'static str,
+    mut body: F,
+) -> impl FnMut(&'a str) -> IResult<&'a str, (u32, O)>
+where
+    F: FnMut(&'a str) -> IResult<&'a str, O>,
+{
+    move |input| {
+        let (input, id) = delimited(pair(tag(label), space1), number, char(':'))(input)?;
+        let (input, _) = space0(input)?;
+        let (input, output) = body(input)?;
+        Ok((input, (id, output)))
+    }
+}
+
+/// A `key: value` line, giving the trimmed key with whatever `value` parses.
+pub fn key_value<'a, O, F>(mut value: F) -> impl FnMut(&'a str) -> IResult<&'a str, (&'a str, O)>
+where
+    F: FnMut(&'a str) -> IResult<&'a str, O>,
+{
+    move |input| {
+        let (input, (key, _, _)) =
+            tuple((take_till1(|c| c == ':' || c == '\n'), char(':'), space0))(input)?;
+        let (input, output) = value(input)?;
+        Ok((input, (key.trim(), output)))
+    }
+}
+
+// a character `T::try_from` rejects fails the whole parse, pointing at that character
+fn tile<T: TryFrom<char>>(input: &str) -> IResult<&str, T> {
+    let (rest, c) = none_of("\r\n")(input)?;
+    match T::try_from(c) {
+        Ok(tile) => Ok((rest, tile)),
+        Err(_) => Err(nom::Err::Failure(Error::new(input, ErrorKind::MapRes))),
+    }
+}
+
+/// Rows of characters, each mapped through `T::try_from`, without checking their widths.
+pub fn rows<T: TryFrom<char>>(input: &str) -> IResult<&str, Vec<Vec<T>>> {
+    lines(many1(tile))(input)
+}
+
+/// A rectangular [`Grid`] of characters, each mapped through `T::try_from`.
+pub fn grid<T: TryFrom<char>>(input: &str) -> IResult<&str, Grid<T>> {
+    map_res(
+        verify(rows, |rows: &Vec<Vec<T>>| rows.iter().all(|row| row.len() == rows[0].len())),
+        Grid::from_rows,
+    )(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, PartialEq)]
+    enum Tile {
+        Open,
+        Wall,
+    }
+
+    impl TryFrom<char> for Tile {
+        type Error = AocError;
+
+        fn try_from(c: char) -> Result<Self, Self::Error> {
+            match c {
+                '.' => Ok(Tile::Open),
+                '#' => Ok(Tile::Wall),
+                _ => Err(AocError::parse(format!("unexpected tile {c:?}"))),
+            }
+        }
+    }
+
+    #[test]
+    fn test_numbers() {
+        assert_eq!(complete("79 14\t55  13\n", numbers::<u32>).unwrap(), [79, 14, 55, 13]);
+        assert_eq!(complete("-2 +4 0", numbers::<i64>).unwrap(), [-2, 4, 0]);
+        assert_eq!(
+            complete("1 2\r\n-3\r\n", lines(numbers::<i8>)).unwrap(),
+            [vec![1, 2], vec![-3]]
+        );
+        assert!(complete("-2", numbers::<u32>).is_err());
+        assert!(complete("300", numbers::<u8>).is_err());
+    }
+
+    #[test]
+    fn test_labelled_and_key_value() {
+        let card = labelled("Card", separated_list1(pair(space0, char('|')), numbers::<u32>));
+        assert_eq!(
+            complete("Card   3: 1 21 | 69 82  5", card).unwrap(),
+            (3, vec![vec![1, 21], vec![69, 82, 5]])
+        );
+        assert_eq!(
+            complete("seed-to-soil map: 50 98", key_value(numbers::<u32>)).unwrap(),
+            ("seed-to-soil map", vec![50, 98])
+        );
+    }
+
+    #[test]
+    fn test_grid() {
+        let tiles = complete(".#\n#.\n", grid::<Tile>).unwrap();
+        assert_eq!(
+            tiles.rows().collect::<Vec<_>>(),
+            [[Tile::Open, Tile::Wall], [Tile::Wall, Tile::Open]]
+        );
+        assert!(complete(".#\n#\n", grid::<Tile>).is_err());
+        let error = complete(".#\n#x\n", grid::<Tile>).unwrap_err();
+        assert_eq!(error.line(), Some(2));
+    }
+}
@@ -0,0 +1,195 @@
+//! Signed grid coordinates and the four directions between them.
+
+use std::{
+    fmt,
+    ops::{Add, AddAssign, Mul, Neg, Sub},
+};
+
+use glam::IVec2;
+
+/// A position on a grid, `x` to the right and `y` down from the top left.
+///
+/// Points may lie off the grid, [`Point::to_index`] is the only way back to `usize`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Point(pub IVec2);
+
+impl Point {
+    pub const ORIGIN: Point = Point(IVec2::ZERO);
+
+    pub const fn new(x: i32, y: i32) -> Self {
+        Point(IVec2::new(x, y))
+    }
+
+    pub fn x(self) -> i32 {
+        self.0.x
+    }
+
+    pub fn y(self) -> i32 {
+        self.0.y
+    }
+
+    /// The point from a `(column, row)` pair of indices, `None` past `i32::MAX`.
+    pub fn from_index(column: usize, row: usize) -> Option<Self> {
+        Some(Point::new(i32::try_from(column).ok()?, i32::try_from(row).ok()?))
+    }
+
+    /// `(column, row)` indices, `None` for a point left of or above the origin.
+    pub fn to_index(self) -> Option<(usize, usize)> {
+        Some((usize::try_from(self.x()).ok()?, usize::try_from(self.y()).ok()?))
+    }
+
+    /// One step towards `direction`.
+    pub fn step(self, direction: Direction) -> Self {
+        self + direction.delta()
+    }
+
+    /// The points one step away in each direction, clockwise from north.
+    pub fn neighbors4(self) -> [Point; 4] {
+        Direction::ALL.map(|direction| self.step(direction))
+    }
+
+    /// [`Point::neighbors4`] followed by the diagonal ones, clockwise from north east.
+    pub fn neighbors8(self) -> [Point; 8] {
+        let [n, e, s, w] = self.neighbors4();
+        [
+            n,
+            e,
+            s,
+            w,
+            n + Direction::East,
+            s + Direction::East,
+            s + Direction::West,
+            n + Direction::West,
+        ]
+    }
+
+    pub fn manhattan(self, other: Point) -> u32 {
+        let d = (self.0 - other.0).abs();
+        d.x.unsigned_abs() + d.y.unsigned_abs()
+    }
+}
+
+impl fmt::Display for Point {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "({}, {})", self.x(), self.y())
+    }
+}
+
+impl Add for Point {
+    type Output = Point;
+
+    fn add(self, other: Point) -> Point {
+        Point(self.0 + other.0)
+    }
+}
+
+impl AddAssign for Point {
+    fn add_assign(&mut self, other: Point) {
+        self.0 += other.0;
+    }
+}
+
+impl Sub for Point {
+    type Output = Point;
+
+    fn sub(self, other: Point) -> Point {
+        Point(self.0 - other.0)
+    }
+}
+
+impl Neg for Point {
+    type Output = Point;
+
+    fn neg(self) -> Point {
+        Point(-self.0)
+    }
+}
+
+impl Mul<i32> for Point {
+    type Output = Point;
+
+    fn mul(self, factor: i32) -> Point {
+        Point(self.0 * factor)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Direction {
+    North,
+    East,
+    South,
+    West,
+}
+
+impl Direction {
+    /// Clockwise from north.
+    pub const ALL: [Direction; 4] =
+        [Direction::North, Direction::East, Direction::South, Direction::West];
+
+    /// The step this direction takes, north is up so its `y` is negative.
+    pub fn delta(self) -> Point {
+        match self {
+            Direction::North => Point::new(0, -1),
+            Direction::East => Point::new(1, 0),
+            Direction::South => Point::new(0, 1),
+            Direction::West => Point::new(-1, 0),
+        }
+    }
+
+    pub fn turn_right(self) -> Self {
+        Direction::ALL[(self as usize + 1) % 4]
+    }
+
+    pub fn turn_left(self) -> Self {
+        Direction::ALL[(self as usize + 3) % 4]
+    }
+
+    pub fn opposite(self) -> Self {
+        Direction::ALL[(self as usize + 2) % 4]
+    }
+}
+
+impl Add<Direction> for Point {
+    type Output = Point;
+
+    fn add(self, direction: Direction) -> Point {
+        self.step(direction)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_directions() {
+        for direction in Direction::ALL {
+            assert_eq!(direction.turn_right().turn_left(), direction);
+            assert_eq!(direction.turn_right().turn_right(), direction.opposite());
+            assert_eq!(direction.opposite().delta(), -direction.delta());
+        }
+        assert_eq!(Direction::North.turn_right(), Direction::East);
+        assert_eq!(Direction::North.turn_left(), Direction::West);
+    }
+
+    #[test]
+    fn test_points() {
+        let p = Point::new(2, 1);
+        assert_eq!(p + Direction::North, Point::new(2, 0));
+        assert_eq!(p.step(Direction::West) + Direction::East, p);
+        assert_eq!(p.neighbors4()[1], Point::new(3, 1));
+        assert_eq!(
+            p.neighbors8()[4..],
+            [Point::new(3, 0), Point::new(3, 2), Point::new(1, 2), Point::new(1, 0)]
+        );
+        assert_eq!(p - Point::new(5, 5), Point::new(-3, -4));
+        assert_eq!(Point::ORIGIN.manhattan(Point::new(-3, 4)), 7);
+        assert_eq!(p * 3, Point::new(6, 3));
+
+        assert_eq!(p.to_index(), Some((2, 1)));
+        assert_eq!(Point::new(-1, 0).to_index(), None);
+        assert_eq!(Point::new(0, i32::MIN).to_index(), None);
+        assert_eq!(Point::from_index(3, 4), Some(Point::new(3, 4)));
+        assert_eq!(Point::from_index(usize::MAX, 0), None);
+    }
+}
@@ -0,0 +1,277 @@
+//! Graph searches over a `successors` closure: BFS, DFS, Dijkstra and A*.
+//!
+//! BFS and DFS count steps, so their successors are plain nodes; Dijkstra and A* take
+//! `(node, cost)` pairs.
+
+use std::{
+    cmp::Ordering,
+    collections::{hash_map::Entry, BinaryHeap, HashMap, VecDeque},
+    hash::Hash,
+    ops::Add,
+};
+
+/// Everything a search reached, how far away it was and where it was reached from.
+#[derive(Debug, Clone)]
+pub struct Search<N, C> {
+    pub distances: HashMap<N, C>,
+    /// The node each node was first reached from, starts have none
+    pub predecessors: HashMap<N, N>,
+}
+
+impl<N: Eq + Hash + Clone, C: Copy> Search<N, C> {
+    fn new() -> Self {
+        Search { distances: HashMap::new(), predecessors: HashMap::new() }
+    }
+
+    pub fn distance(&self, node: &N) -> Option<C> {
+        self.distances.get(node).copied()
+    }
+
+    pub fn contains(&self, node: &N) -> bool {
+        self.distances.contains_key(node)
+    }
+
+    /// How many nodes were reached, starts included.
+    pub fn len(&self) -> usize {
+        self.distances.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.distances.is_empty()
+    }
+
+    /// The nodes from a start to `goal`, `None` if it was not reached.
+    pub fn path_to(&self, goal: &N) -> Option<Vec<N>> {
+        if !self.contains(goal) {
+            return None;
+        }
+        let mut path = vec![goal.clone()];
+        while let Some(previous) = self.predecessors.get(path.last()?) {
+            path.push(previous.clone());
+        }
+        path.reverse();
+        Some(path)
+    }
+}
+
+impl<N: Eq + Hash + Clone> Search<N, usize> {
+    /// The largest distance, how far the farthest node is for a BFS.
+    pub fn max_distance(&self) -> Option<usize> {
+        self.distances.values().copied().max()
+    }
+}
+
+/// Breadth first from every start, so distances are the fewest steps to each node.
+pub fn bfs<N, I>(
+    starts: impl IntoIterator<Item = N>,
+    mut successors: impl FnMut(&N) -> I,
+) -> Search<N, usize>
+where
+    N: Eq + Hash + Clone,
+    I: IntoIterator<Item = N>,
+{
+    let mut search = Search::new();
+    let mut queue = VecDeque::new();
+    for start in starts {
+        if search.distances.insert(start.clone(), 0).is_none() {
+            queue.push_back(start);
+        }
+    }
+    while let Some(node) = queue.pop_front() {
+        let distance = search.distances[&node] + 1;
+        for next in successors(&node) {
+            if let Entry::Vacant(entry) = search.distances.entry(next.clone()) {
+                entry.insert(distance);
+                search.predecessors.insert(next.clone(), node.clone());
+                queue.push_back(next);
+            }
+        }
+    }
+    search
+}
+
+/// Depth first from every start; distances are the depth each node was found at, which
+/// need not be the shortest.
+pub fn dfs<N, I>(
+    starts: impl IntoIterator<Item = N>,
+    mut successors: impl FnMut(&N) -> I,
+) -> Search<N, usize>
+where
+    N: Eq + Hash + Clone,
+    I: IntoIterator<Item = N>,
+{
+    let mut search = Search::new();
+    let mut stack = vec![];
+    for start in starts {
+        if search.distances.insert(start.clone(), 0).is_none() {
+            stack.push(start);
+        }
+    }
+    while let Some(node) = stack.pop() {
+        let depth = search.distances[&node] + 1;
+        for next in successors(&node) {
+            if let Entry::Vacant(entry) = search.distances.entry(next.clone()) {
+                entry.insert(depth);
+                search.predecessors.insert(next.clone(), node.clone());
+                stack.push(next);
+            }
+        }
+    }
+    search
+}
+
+// a frontier entry, ordered so the `BinaryHeap` pops the lowest priority first
+struct Frontier<N, C> {
+    priority: C,
+    cost: C,
+    node: N,
+}
+
+impl<N, C: Ord> PartialEq for Frontier<N, C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.priority == other.priority
+    }
+}
+
+impl<N, C: Ord> Eq for Frontier<N, C> {}
+
+impl<N, C: Ord> PartialOrd for Frontier<N, C> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<N, C: Ord> Ord for Frontier<N, C> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.priority.cmp(&self.priority)
+    }
+}
+
+// best first search, expanding by cost plus `heuristic` until `is_goal` accepts a node
+fn best_first<N, C, I>(
+    starts: impl IntoIterator<Item = N>,
+    mut successors: impl FnMut(&N) -> I,
+    mut heuristic: impl FnMut(&N) -> C,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> (Search<N, C>, Option<N>)
+where
+    N: Eq + Hash + Clone,
+    C: Ord + Copy + Default + Add<Output = C>,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let mut search = Search::new();
+    let mut frontier = BinaryHeap::new();
+    for start in starts {
+        search.distances.insert(start.clone(), C::default());
+        frontier.push(Frontier { priority: heuristic(&start), cost: C::default(), node: start });
+    }
+    while let Some(Frontier { cost, node, .. }) = frontier.pop() {
+        if search.distances.get(&node).is_some_and(|best| *best < cost) {
+            continue;
+        }
+        if is_goal(&node) {
+            return (search, Some(node));
+        }
+        for (next, step) in successors(&node) {
+            let cost = cost + step;
+            if search.distances.get(&next).is_some_and(|best| *best <= cost) {
+                continue;
+            }
+            search.distances.insert(next.clone(), cost);
+            search.predecessors.insert(next.clone(), node.clone());
+            frontier.push(Frontier { priority: cost + heuristic(&next), cost, node: next });
+        }
+    }
+    (search, None)
+}
+
+/// The cheapest cost from any start to every reachable node.
+pub fn dijkstra<N, C, I>(
+    starts: impl IntoIterator<Item = N>,
+    successors: impl FnMut(&N) -> I,
+) -> Search<N, C>
+where
+    N: Eq + Hash + Clone,
+    C: Ord + Copy + Default + Add<Output = C>,
+    I: IntoIterator<Item = (N, C)>,
+{
+    best_first(starts, successors, |_| C::default(), |_| false).0
+}
+
+/// The cheapest path from `start` to a node accepted by `is_goal` and its cost.
+///
+/// `heuristic` must never overestimate the remaining cost, or the path may not be the
+/// cheapest.
+pub fn astar<N, C, I>(
+    start: N,
+    successors: impl FnMut(&N) -> I,
+    heuristic: impl FnMut(&N) -> C,
+    is_goal: impl FnMut(&N) -> bool,
+) -> Option<(Vec<N>, C)>
+where
+    N: Eq + Hash + Clone,
+    C: Ord + Copy + Default + Add<Output = C>,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let (search, goal) = best_first([start], successors, heuristic, is_goal);
+    let goal = goal?;
+    Some((search.path_to(&goal)?, search.distance(&goal)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::{
+        grid::{Cells, Grid},
+        point::Point,
+    };
+
+    // `#` are walls, digits the cost of entering a tile
+    fn maze() -> Grid<char> {
+        "1#111\n1#1#1\n19191\n111#1\n".parse().unwrap()
+    }
+
+    fn open(grid: &Grid<char>, point: Point) -> impl Iterator<Item = Point> + '_ {
+        grid.neighbors4(point).filter(|next| grid[*next] != '#')
+    }
+
+    fn weighted(grid: &Grid<char>, point: Point) -> impl Iterator<Item = (Point, u32)> + '_ {
+        open(grid, point).map(|next| (next, grid[next].to_digit(10).unwrap()))
+    }
+
+    #[test]
+    fn test_bfs_and_dfs() {
+        let grid = maze();
+        let goal = Point::new(4, 0);
+        let search = bfs([Point::ORIGIN], |p| open(&grid, *p));
+        assert_eq!(search.distance(&goal), Some(8));
+        assert_eq!(search.len(), 16);
+        assert_eq!(search.max_distance(), Some(8));
+        let path = search.path_to(&goal).unwrap();
+        assert_eq!((path.first(), path.last(), path.len()), (Some(&Point::ORIGIN), Some(&goal), 9));
+        assert!(path.windows(2).all(|w| w[0].manhattan(w[1]) == 1));
+        assert_eq!(search.path_to(&Point::new(1, 0)), None);
+
+        let flood = dfs([Point::ORIGIN, Point::new(4, 3)], |p| open(&grid, *p));
+        assert_eq!(flood.len(), 16);
+        assert_eq!(flood.path_to(&Point::new(4, 3)), Some(vec![Point::new(4, 3)]));
+    }
+
+    #[test]
+    fn test_dijkstra_and_astar() {
+        let grid = maze();
+        let goal = Point::new(4, 0);
+        let search = dijkstra([Point::ORIGIN], |p| weighted(&grid, *p));
+        // the two 9s make the short way through the middle more expensive than going around
+        assert_eq!(search.distance(&goal), Some(10));
+        assert_eq!(search.distance(&Point::new(2, 2)), Some(6));
+
+        let (path, cost) =
+            astar(Point::ORIGIN, |p| weighted(&grid, *p), |p| p.manhattan(goal), |p| *p == goal)
+                .unwrap();
+        assert_eq!(cost, 10);
+        assert_eq!(path.len(), 11);
+        let wall = Point::new(1, 0);
+        assert_eq!(astar(Point::ORIGIN, |p| weighted(&grid, *p), |_| 0, |p| *p == wall), None);
+    }
+}
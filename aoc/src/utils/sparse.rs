@@ -0,0 +1,162 @@
+//! A grid of scattered cells on unbounded coordinates, for when most points hold nothing.
+
+use std::{collections::HashMap, fmt, ops::Index};
+
+use crate::utils::{
+    grid::{write_cells, Cells},
+    point::Point,
+};
+
+/// Cells keyed by [`Point`], with bounds that grow and shrink with them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SparseGrid<T> {
+    cells: HashMap<Point, T>,
+    // the top left and bottom right corners around every cell, kept exact
+    bounds: Option<(Point, Point)>,
+}
+
+impl<T> Default for SparseGrid<T> {
+    fn default() -> Self {
+        SparseGrid { cells: HashMap::new(), bounds: None }
+    }
+}
+
+impl<T> SparseGrid<T> {
+    pub fn new() -> Self {
+        SparseGrid::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    pub fn contains(&self, point: Point) -> bool {
+        self.cells.contains_key(&point)
+    }
+
+    pub fn get(&self, point: Point) -> Option<&T> {
+        self.cells.get(&point)
+    }
+
+    pub fn get_mut(&mut self, point: Point) -> Option<&mut T> {
+        self.cells.get_mut(&point)
+    }
+
+    /// Set the cell at `point`, returning the one it replaced.
+    pub fn insert(&mut self, point: Point, cell: T) -> Option<T> {
+        self.bounds = Some(match self.bounds {
+            Some((min, max)) => (Point(min.0.min(point.0)), Point(max.0.max(point.0))),
+            None => (point, point),
+        });
+        self.cells.insert(point, cell)
+    }
+
+    pub fn remove(&mut self, point: Point) -> Option<T> {
+        let cell = self.cells.remove(&point)?;
+        // only a cell on the edge can move the bounds in
+        if let Some((min, max)) = self.bounds {
+            let edges =
+                [min.x(), max.x()].contains(&point.x()) || [min.y(), max.y()].contains(&point.y());
+            if edges {
+                self.bounds = self.cells.keys().fold(None, |bounds, p| match bounds {
+                    Some((min, max)) => Some((Point(p.0.min(min.0)), Point(p.0.max(max.0)))),
+                    None => Some((*p, *p)),
+                });
+            }
+        }
+        Some(cell)
+    }
+
+    /// Every cell with its point, in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+        self.cells.iter().map(|(point, cell)| (*point, cell))
+    }
+
+    pub fn points(&self) -> impl Iterator<Item = Point> + '_ {
+        self.cells.keys().copied()
+    }
+}
+
+impl<T> Cells for SparseGrid<T> {
+    type Cell = T;
+
+    fn get(&self, point: Point) -> Option<&T> {
+        SparseGrid::get(self, point)
+    }
+
+    fn bounds(&self) -> Option<(Point, Point)> {
+        self.bounds
+    }
+}
+
+impl<T> Index<Point> for SparseGrid<T> {
+    type Output = T;
+
+    fn index(&self, point: Point) -> &T {
+        match self.get(point) {
+            Some(cell) => cell,
+            None => panic!("no cell at {point}"),
+        }
+    }
+}
+
+impl<T> FromIterator<(Point, T)> for SparseGrid<T> {
+    fn from_iter<I: IntoIterator<Item = (Point, T)>>(cells: I) -> Self {
+        let mut grid = SparseGrid::new();
+        grid.extend(cells);
+        grid
+    }
+}
+
+impl<T> Extend<(Point, T)> for SparseGrid<T> {
+    fn extend<I: IntoIterator<Item = (Point, T)>>(&mut self, cells: I) {
+        for (point, cell) in cells {
+            self.insert(point, cell);
+        }
+    }
+}
+
+/// The rectangle around every cell, one row per line with `.` for the gaps.
+impl<T: fmt::Display> fmt::Display for SparseGrid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.bounds {
+            Some((min, max)) => write_cells(f, self, min, max),
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::grid::Grid;
+
+    #[test]
+    fn test_sparse_grid() {
+        let grid: Grid<char> = ".#.\n..#\n#..\n".parse().unwrap();
+        let mut rocks: SparseGrid<char> =
+            grid.iter().filter(|(_, c)| **c == '#').map(|(p, c)| (p, *c)).collect();
+        assert_eq!(rocks.len(), 3);
+        assert_eq!(rocks.to_string(), grid.to_string());
+        assert_eq!(rocks.neighbors8(Point::new(1, 0)).collect::<Vec<_>>(), [Point::new(2, 1)]);
+
+        rocks.insert(Point::new(-2, -1), '@');
+        assert_eq!(rocks.bounds(), Some((Point::new(-2, -1), Point::new(2, 2))));
+        assert_eq!(rocks.to_string(), "@....\n...#.\n....#\n..#..\n");
+        assert_eq!(rocks.remove(Point::new(-2, -1)), Some('@'));
+        assert_eq!(rocks.remove(Point::new(-2, -1)), None);
+        assert_eq!(rocks.bounds(), Some((Point::ORIGIN, Point::new(2, 2))));
+        assert_eq!(rocks.render(Point::new(1, 1), Point::new(2, 2)), ".#\n..\n");
+
+        for point in grid.points() {
+            rocks.remove(point);
+        }
+        assert!(rocks.is_empty());
+        assert_eq!(rocks.bounds(), None);
+        assert_eq!(rocks.to_string(), "");
+    }
+}
@@ -0,0 +1,18 @@
+//! proptest strategies shared by the range property tests of the days.
+
+use proptest::prelude::*;
+
+/// Small values make overlaps likely, the rest cover the edges of u32.
+pub fn bound() -> impl Strategy<Value = usize> {
+    prop_oneof![
+        0usize..32,
+        Just(u32::MAX as usize),
+        (u32::MAX as usize - 32)..=u32::MAX as usize,
+        any::<u32>().prop_map(|x| x as usize),
+    ]
+}
+
+/// A range as `(start, length)`, empty ones included.
+pub fn start_and_length() -> impl Strategy<Value = (usize, usize)> {
+    (bound(), prop_oneof![Just(0usize), 1usize..32, bound()])
+}
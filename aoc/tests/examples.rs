@@ -0,0 +1,20 @@
+use std::{fmt::Display, path::Path};
+
+use aoc::{error::AocError, utils::read_input};
+
+fn check_example<T: Display>(
+    example: &str,
+    part: &str,
+    expected: &str,
+    solve: fn(&str) -> Result<T, AocError>,
+) -> Result<(), AocError> {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("..").join("examples").join(example);
+    let actual = solve(&read_input(&path)?)?.to_string();
+    if actual != expected {
+        let part = format!("{example} {part}");
+        return Err(AocError::Mismatch { part, expected: expected.to_owned(), actual });
+    }
+    Ok(())
+}
+
+include!(concat!(env!("OUT_DIR"), "/examples.rs"));
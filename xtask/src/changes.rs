@@ -0,0 +1,243 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use aoc_common::diff::diff_lines;
+
+use crate::AocError;
+
+// one file before and after the change, `None` where it does not exist
+#[derive(Debug)]
+struct Edit {
+    path: PathBuf,
+    before: Option<String>,
+    after: Option<String>,
+    moved_from: Option<(PathBuf, String)>,
+}
+
+impl Edit {
+    fn apply(&self) -> Result<(), AocError> {
+        write_or_remove(&self.path, self.after.as_deref())
+    }
+
+    fn revert(&self) -> Result<(), AocError> {
+        write_or_remove(&self.path, self.before.as_deref())
+    }
+}
+
+fn write_or_remove(path: &Path, contents: Option<&str>) -> Result<(), AocError> {
+    match contents {
+        Some(contents) => {
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::write(path, contents)?;
+        }
+        None if path.exists() => fs::remove_file(path)?,
+        None => {}
+    }
+    Ok(())
+}
+
+/// Edits to the tree that are planned in full, shown as a diff and then applied together.
+#[derive(Debug, Default)]
+pub struct ChangeSet {
+    edits: Vec<Edit>,
+    empty_dirs: Vec<PathBuf>,
+    moved_dirs: Vec<(PathBuf, PathBuf)>,
+    deleted_dirs: Vec<PathBuf>,
+}
+
+impl ChangeSet {
+    fn edit(&mut self, path: &Path) -> Result<&mut Edit, AocError> {
+        let index = match self.edits.iter().position(|e| e.path == path) {
+            Some(index) => index,
+            None => {
+                let before = if path.exists() { Some(fs::read_to_string(path)?) } else { None };
+                let after = before.clone();
+                self.edits.push(Edit { path: path.to_owned(), before, after, moved_from: None });
+                self.edits.len() - 1
+            }
+        };
+        Ok(&mut self.edits[index])
+    }
+
+    /// Contents of `path` as of the planned changes.
+    pub fn read(&mut self, path: &Path) -> Result<Option<String>, AocError> {
+        Ok(self.edit(path)?.after.clone())
+    }
+
+    pub fn write(&mut self, path: &Path, contents: String) -> Result<(), AocError> {
+        self.edit(path)?.after = Some(contents);
+        Ok(())
+    }
+
+    pub fn remove(&mut self, path: &Path) -> Result<(), AocError> {
+        self.edit(path)?.after = None;
+        Ok(())
+    }
+
+    pub fn rename(&mut self, from: &Path, to: &Path) -> Result<(), AocError> {
+        let Some(contents) = self.edit(from)?.after.take() else {
+            return Err(format!("{} does not exist", from.display()).into());
+        };
+        let edit = self.edit(to)?;
+        if edit.after.is_some() {
+            return Err(format!("{} already exists", to.display()).into());
+        }
+        edit.after = Some(contents.clone());
+        edit.moved_from = Some((from.to_owned(), contents));
+        Ok(())
+    }
+
+    /// Remove every file in `dir` and the then empty directory.
+    pub fn remove_dir(&mut self, dir: &Path) -> Result<(), AocError> {
+        for path in dir_files(dir)? {
+            self.remove(&path)?;
+        }
+        self.empty_dirs.push(dir.to_owned());
+        Ok(())
+    }
+
+    /// Move every file in `from` into `to`.
+    pub fn rename_dir(&mut self, from: &Path, to: &Path) -> Result<(), AocError> {
+        if to.exists() {
+            return Err(format!("{} already exists", to.display()).into());
+        }
+        for path in dir_files(from)? {
+            self.rename(&path, &to.join(path.file_name().unwrap()))?;
+        }
+        self.empty_dirs.push(from.to_owned());
+        Ok(())
+    }
+
+    /// Move `from` to `to` as a whole once the edits are applied, for directories like fuzz
+    /// corpora whose files need not be text.
+    pub fn move_dir(&mut self, from: &Path, to: &Path) -> Result<(), AocError> {
+        if to.exists() {
+            return Err(format!("{} already exists", to.display()).into());
+        }
+        self.moved_dirs.push((from.to_owned(), to.to_owned()));
+        Ok(())
+    }
+
+    /// Delete `dir` with everything in it once the edits are applied, for directories like fuzz
+    /// corpora whose files need not be text.
+    pub fn delete_dir(&mut self, dir: &Path) {
+        self.deleted_dirs.push(dir.to_owned());
+    }
+
+    /// A summary of the change with a line diff for every modified file.
+    pub fn render(&self, root: &Path) -> String {
+        let show = |path: &Path| path.strip_prefix(root).unwrap_or(path).display().to_string();
+        let mut out = String::new();
+        for edit in &self.edits {
+            let path = show(&edit.path);
+            match (&edit.before, &edit.after, &edit.moved_from) {
+                (_, Some(after), Some((from, original))) => {
+                    out.push_str(&format!("renamed {} -> {path}\n", show(from)));
+                    out.push_str(&diff_lines(original, after));
+                }
+                (None, Some(after), None) => {
+                    out.push_str(&format!("created {path} ({} lines)\n", after.lines().count()))
+                }
+                (Some(_), None, _) if self.is_moved(&edit.path) => {}
+                (Some(_), None, _) => out.push_str(&format!("deleted {path}\n")),
+                (Some(before), Some(after), None) if before != after => {
+                    out.push_str(&format!("modified {path}\n"));
+                    out.push_str(&diff_lines(before, after));
+                }
+                _ => {}
+            }
+        }
+        for (from, to) in &self.moved_dirs {
+            out.push_str(&format!("renamed {}/ -> {}/\n", show(from), show(to)));
+        }
+        for dir in &self.deleted_dirs {
+            out.push_str(&format!("deleted {}/\n", show(dir)));
+        }
+        out
+    }
+
+    fn is_moved(&self, path: &Path) -> bool {
+        self.edits.iter().any(|e| e.moved_from.as_ref().is_some_and(|(from, _)| from == path))
+    }
+
+    /// Apply every edit, restoring the files already written when one of them fails.
+    pub fn apply(self) -> Result<(), AocError> {
+        for (i, edit) in self.edits.iter().enumerate() {
+            if let Err(e) = edit.apply() {
+                for done in self.edits[..i].iter().rev() {
+                    done.revert()?;
+                }
+                return Err(e);
+            }
+        }
+        for dir in &self.empty_dirs {
+            if dir.exists() {
+                fs::remove_dir(dir)?;
+            }
+        }
+        for (from, to) in &self.moved_dirs {
+            fs::rename(from, to)?;
+        }
+        for dir in &self.deleted_dirs {
+            fs::remove_dir_all(dir)?;
+        }
+        Ok(())
+    }
+}
+
+fn dir_files(dir: &Path) -> Result<Vec<PathBuf>, AocError> {
+    if !dir.is_dir() {
+        return Ok(Vec::new());
+    }
+    let mut files = Vec::new();
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            return Err(format!("unexpected directory {}", path.display()).into());
+        }
+        files.push(path);
+    }
+    files.sort();
+    Ok(files)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_change_set() {
+        let root = std::env::temp_dir().join(format!("xtask-changes-{}", std::process::id()));
+        fs::create_dir_all(root.join("old")).unwrap();
+        fs::write(root.join("old/a.txt"), "a\n").unwrap();
+        fs::create_dir_all(root.join("corpus")).unwrap();
+        fs::write(root.join("corpus/crash"), [0xff, 0xfe]).unwrap();
+        fs::write(root.join("mod.rs"), "pub mod old;\n").unwrap();
+
+        let mut changes = ChangeSet::default();
+        changes.rename_dir(&root.join("old"), &root.join("new")).unwrap();
+        changes.write(&root.join("new/a.txt"), "b\n".to_owned()).unwrap();
+        changes.write(&root.join("mod.rs"), "pub mod new;\n".to_owned()).unwrap();
+        changes.move_dir(&root.join("corpus"), &root.join("corpus2")).unwrap();
+        fs::create_dir_all(root.join("artifacts/crash")).unwrap();
+        changes.delete_dir(&root.join("artifacts"));
+        assert_eq!(
+            changes.render(&root),
+            "renamed old/a.txt -> new/a.txt\n    1 - a\n    1 + b\n\
+             modified mod.rs\n    1 - pub mod old;\n    1 + pub mod new;\n\
+             renamed corpus/ -> corpus2/\ndeleted artifacts/\n"
+        );
+        changes.apply().unwrap();
+
+        assert!(!root.join("old").exists());
+        assert_eq!(fs::read_to_string(root.join("new/a.txt")).unwrap(), "b\n");
+        assert_eq!(fs::read_to_string(root.join("mod.rs")).unwrap(), "pub mod new;\n");
+        assert_eq!(fs::read(root.join("corpus2/crash")).unwrap(), [0xff, 0xfe]);
+        assert!(!root.join("artifacts").exists());
+        fs::remove_dir_all(root).unwrap();
+    }
+}
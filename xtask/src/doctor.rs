@@ -0,0 +1,243 @@
+use std::{collections::BTreeMap, fmt, fs, path::Path, process::Command};
+
+use clap::Parser;
+
+use crate::{project_root, AocError, CommandImpl};
+
+#[derive(Parser, Debug)]
+pub struct Doctor {
+    /// Fail on warnings as well as errors
+    #[clap(long)]
+    strict: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    Warning,
+    Error,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Finding {
+    pub severity: Severity,
+    pub message: String,
+}
+
+impl fmt::Display for Finding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.severity {
+            Severity::Warning => write!(f, "warning: {}", self.message),
+            Severity::Error => write!(f, "error: {}", self.message),
+        }
+    }
+}
+
+/// What the checks look at, read from disk once.
+#[derive(Debug, Default)]
+pub struct Workspace {
+    /// Day module names and sources in `aoc/src/commands`
+    pub modules: BTreeMap<String, String>,
+    pub mod_rs: String,
+    /// Example stems per day and whether each has an answers file with a part recorded
+    pub examples: BTreeMap<String, Vec<(String, bool)>>,
+    pub inputs: Vec<String>,
+    /// Files under `input/` tracked by git, `None` outside a git checkout
+    pub committed_inputs: Option<Vec<String>>,
+}
+
+impl Workspace {
+    pub fn read(root: &Path) -> Result<Workspace, AocError> {
+        let mut workspace = Workspace::default();
+        let commands = root.join("aoc").join("src").join("commands");
+        for entry in fs::read_dir(&commands)? {
+            let path = entry?.path();
+            let Some(stem) = path.file_stem().and_then(|s| s.to_str()) else { continue };
+            if path.extension().is_some_and(|e| e == "rs") && stem != "mod" {
+                workspace.modules.insert(stem.to_owned(), fs::read_to_string(&path)?);
+            }
+        }
+        workspace.mod_rs = fs::read_to_string(commands.join("mod.rs"))?;
+
+        let examples = root.join("examples");
+        for day in fs::read_dir(&examples).into_iter().flatten() {
+            let day = day?.path();
+            // a stray README or .gitkeep next to the day directories
+            if !day.is_dir() {
+                continue;
+            }
+            let name = day.file_name().unwrap().to_string_lossy().into_owned();
+            let mut stems = Vec::new();
+            for example in fs::read_dir(&day)? {
+                let example = example?.path();
+                if example.extension().is_some_and(|e| e == "txt") {
+                    let answers = fs::read_to_string(example.with_extension("answers"))
+                        .unwrap_or_default()
+                        .lines()
+                        .any(|l| l.trim_start().starts_with("part"));
+                    let stem = example.file_stem().unwrap().to_string_lossy().into_owned();
+                    stems.push((stem, answers));
+                }
+            }
+            stems.sort();
+            workspace.examples.insert(name, stems);
+        }
+
+        for input in fs::read_dir(root.join("input")).into_iter().flatten() {
+            workspace.inputs.push(input?.file_name().to_string_lossy().into_owned());
+        }
+        let output = Command::new("git").args(["ls-files", "input"]).current_dir(root).output();
+        workspace.committed_inputs = output
+            .ok()
+            .filter(|o| o.status.success())
+            .map(|o| String::from_utf8_lossy(&o.stdout).lines().map(str::to_owned).collect());
+        Ok(workspace)
+    }
+}
+
+// `pub mod day1;` modules and the `(variant, module)` of `Day1(day1::Day1),` variants
+pub fn registrations(mod_rs: &str) -> (Vec<&str>, Vec<(&str, &str)>) {
+    let modules =
+        mod_rs.lines().filter_map(|l| l.strip_prefix("pub mod ")?.strip_suffix(';')).collect();
+    let variants = mod_rs
+        .lines()
+        .skip_while(|l| !l.starts_with("pub enum SubCommand"))
+        .skip(1)
+        .take_while(|l| !l.starts_with('}'))
+        .filter_map(|l| {
+            let (variant, rest) = l.trim().split_once('(')?;
+            Some((variant, rest.split_once("::")?.0))
+        })
+        .collect();
+    (modules, variants)
+}
+
+pub fn diagnose(workspace: &Workspace) -> Vec<Finding> {
+    let mut findings = Vec::new();
+    let mut report = |severity, message: String| findings.push(Finding { severity, message });
+    let (declared, variants) = registrations(&workspace.mod_rs);
+
+    for (day, source) in &workspace.modules {
+        if !declared.contains(&day.as_str()) {
+            report(Severity::Error, format!("{day}.rs is not declared in commands/mod.rs"));
+        } else if !variants.iter().any(|(_, module)| module == day) {
+            report(Severity::Error, format!("{day} has no SubCommand variant"));
+        }
+
+        match workspace.examples.get(day) {
+            None => report(Severity::Warning, format!("{day} has no examples/{day}/")),
+            Some(examples) if examples.is_empty() => {
+                report(Severity::Warning, format!("{day} has no example files"))
+            }
+            Some(examples) => {
+                for (stem, _) in examples.iter().filter(|(_, answered)| !answered) {
+                    report(Severity::Warning, format!("examples/{day}/{stem}.txt has no answers"));
+                }
+            }
+        }
+
+        if !workspace.inputs.contains(&format!("{day}.txt")) {
+            report(Severity::Warning, format!("{day} has no input/{day}.txt"));
+        }
+
+        // a placeholder while the day is being worked on, not a broken workspace
+        let tests = source.split_once("#[cfg(test)]").map(|(_, tests)| tests);
+        if tests.is_some_and(|tests| tests.contains("todo!(")) {
+            report(Severity::Warning, format!("{day} has a todo!() in its tests"));
+        }
+    }
+    for day in &declared {
+        if !workspace.modules.contains_key(*day) {
+            report(Severity::Error, format!("{day} is declared in commands/mod.rs but missing"));
+        }
+    }
+
+    match &workspace.committed_inputs {
+        Some(committed) => {
+            for input in committed {
+                report(Severity::Error, format!("{input} is committed to git"));
+            }
+        }
+        None => report(Severity::Warning, "not a git checkout, committed inputs unchecked".into()),
+    }
+    findings
+}
+
+impl CommandImpl for Doctor {
+    fn main(&self) -> Result<(), AocError> {
+        let findings = diagnose(&Workspace::read(&project_root())?);
+        for finding in &findings {
+            println!("{finding}");
+        }
+        let errors = findings.iter().filter(|f| f.severity == Severity::Error).count();
+        let warnings = findings.len() - errors;
+        println!("{errors} errors, {warnings} warnings");
+        if errors > 0 || (self.strict && warnings > 0) {
+            return Err("the workspace is inconsistent".into());
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_diagnose() {
+        let workspace = Workspace {
+            modules: [
+                ("day1", "pub struct Day1;\n#[cfg(test)]\nmod tests {}\n"),
+                ("day2", "#[cfg(test)]\nfn test() { todo!() }\n"),
+                ("day3", ""),
+            ]
+            .into_iter()
+            .map(|(day, source)| (day.to_owned(), source.to_owned()))
+            .collect(),
+            mod_rs: "pub mod day1;\npub mod day2;\npub mod day4;\n\n\
+                     pub enum SubCommand {\n    Day1(day1::Day1),\n}\n"
+                .to_owned(),
+            examples: [
+                ("day1".to_owned(), vec![("small".to_owned(), true)]),
+                ("day2".to_owned(), vec![("small".to_owned(), false)]),
+            ]
+            .into_iter()
+            .collect(),
+            inputs: vec!["day1.txt".to_owned(), "day2.txt".to_owned(), "day3.txt".to_owned()],
+            committed_inputs: Some(vec!["input/day3.txt".to_owned()]),
+        };
+
+        let messages: Vec<String> = diagnose(&workspace).iter().map(|f| f.to_string()).collect();
+        assert_eq!(
+            messages,
+            [
+                "error: day2 has no SubCommand variant",
+                "warning: examples/day2/small.txt has no answers",
+                "warning: day2 has a todo!() in its tests",
+                "error: day3.rs is not declared in commands/mod.rs",
+                "warning: day3 has no examples/day3/",
+                "error: day4 is declared in commands/mod.rs but missing",
+                "error: input/day3.txt is committed to git",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_read_workspace() {
+        let root = std::env::temp_dir().join(format!("xtask-doctor-{}", std::process::id()));
+        let commands = root.join("aoc/src/commands");
+        fs::create_dir_all(&commands).unwrap();
+        fs::create_dir_all(root.join("examples/day1")).unwrap();
+        fs::write(commands.join("mod.rs"), "pub mod day1;\n").unwrap();
+        fs::write(commands.join("day1.rs"), "pub struct Day1;\n").unwrap();
+        fs::write(root.join("examples/day1/small.txt"), "1\n").unwrap();
+        fs::write(root.join("examples/day1/small.answers"), "part1: 1\n").unwrap();
+        fs::write(root.join("examples/README.md"), "examples per day\n").unwrap();
+
+        let workspace = Workspace::read(&root).unwrap();
+        assert_eq!(workspace.modules.keys().collect::<Vec<_>>(), ["day1"]);
+        assert_eq!(workspace.mod_rs, "pub mod day1;\n");
+        assert_eq!(workspace.examples.len(), 1);
+        assert_eq!(workspace.examples["day1"], [("small".to_owned(), true)]);
+        fs::remove_dir_all(root).unwrap();
+    }
+}
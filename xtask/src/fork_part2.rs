@@ -0,0 +1,209 @@
+use std::{fs, ops::Range};
+
+use clap::Parser;
+
+use crate::{
+    commands_dir,
+    new_day::{register_day, validate_day_name},
+    project_root,
+    rename_day::rename_in_source,
+    rewrite::word_matches,
+    AocError, CommandImpl,
+};
+
+#[derive(Parser, Debug)]
+pub struct ForkPart2 {
+    /// Day to fork, its part 1 stays untouched
+    #[clap(long, short)]
+    name: String,
+    /// Module for the part two copy, `<name>b` by default
+    #[clap(long)]
+    into: Option<String>,
+}
+
+impl CommandImpl for ForkPart2 {
+    fn main(&self) -> Result<(), AocError> {
+        let into = self.into.clone().unwrap_or_else(|| format!("{}b", self.name));
+        validate_day_name(&self.name)?;
+        validate_day_name(&into)?;
+        let commands = commands_dir();
+        let source_path = commands.join(format!("{}.rs", self.name));
+        let dest = commands.join(format!("{into}.rs"));
+        let examples = project_root().join("examples").join(&self.name);
+        let dest_examples = project_root().join("examples").join(&into);
+        if !source_path.exists() {
+            return Err(format!("{} does not exist", source_path.display()).into());
+        }
+        for path in [&dest, &dest_examples] {
+            if path.exists() {
+                return Err(format!("{} already exists", path.display()).into());
+            }
+        }
+
+        let source = fs::read_to_string(&source_path)?;
+        let forked = fork_source(&source, &self.name, &into)?;
+        let linked = link_part2(&source, &into)?;
+        let mod_rs = fs::read_to_string(commands.join("mod.rs"))?;
+        let registered = register_day(&mod_rs, &into)?;
+
+        fs::write(&dest, forked)?;
+        fs::write(&source_path, linked)?;
+        fs::write(commands.join("mod.rs"), registered)?;
+        fs::create_dir_all(&dest_examples)?;
+        if examples.is_dir() {
+            for entry in fs::read_dir(&examples)? {
+                let entry = entry?;
+                fs::copy(entry.path(), dest_examples.join(entry.file_name()))?;
+            }
+        }
+        // the copy's tests check the renamed `<into>_*` snapshots against the original's output
+        let snapshots = project_root().join("snapshots");
+        if snapshots.is_dir() {
+            for entry in fs::read_dir(&snapshots)? {
+                let path = entry?.path();
+                let Some(file_name) = path.file_name().and_then(|s| s.to_str()) else {
+                    continue;
+                };
+                if let Some(rest) = file_name.strip_prefix(&format!("{}_", self.name)) {
+                    let copy = snapshots.join(format!("{into}_{rest}"));
+                    if !copy.exists() {
+                        fs::copy(&path, &copy)?;
+                        println!("wrote snapshots/{into}_{rest}");
+                    }
+                }
+            }
+        }
+
+        for path in [&dest, &source_path, &commands.join("mod.rs"), &dest_examples] {
+            println!("wrote {}", path.strip_prefix(project_root()).unwrap_or(path).display());
+        }
+        Ok(())
+    }
+}
+
+// the copy runs part 1 from the original day and keeps its own part 2
+fn fork_source(source: &str, from: &str, into: &str) -> Result<String, AocError> {
+    qualify_call(&rename_in_source(source, from, into), "part1", from)
+}
+
+// the original day runs part 2 from its copy
+fn link_part2(source: &str, into: &str) -> Result<String, AocError> {
+    qualify_call(source, "part2", into)
+}
+
+// the body of `fn main` in the day's `impl CommandImpl`, braces included
+fn main_body(source: &str) -> Option<Range<usize>> {
+    let imp = source.find("impl CommandImpl for ")?;
+    let open = imp + source[imp..].find("fn main(")?;
+    let open = open + source[open..].find('{')?;
+    let mut depth = 0;
+    for (i, c) in source[open..].char_indices() {
+        match c {
+            '{' => depth += 1,
+            '}' if depth == 1 => return Some(open..open + i + 1),
+            '}' => depth -= 1,
+            _ => {}
+        }
+    }
+    None
+}
+
+// `part(` calls in the day's main become `super::<module>::part(`, already qualified
+// calls are left alone
+fn qualify_call(source: &str, part: &str, module: &str) -> Result<String, AocError> {
+    let Some(body) = main_body(source) else {
+        return Err("no `fn main` in an `impl CommandImpl` of the day".into());
+    };
+    let main = &source[body.clone()];
+    let calls: Vec<usize> = word_matches(main, part)
+        .into_iter()
+        .filter(|&i| main[i + part.len()..].starts_with('(') && !main[..i].ends_with("::"))
+        .collect();
+    if calls.is_empty() {
+        return Err(format!("expected a `{part}(` call in the day's main").into());
+    }
+    let mut out = source[..body.start].to_owned();
+    let mut last = 0;
+    for i in calls {
+        out.push_str(&main[last..i]);
+        out.push_str(&format!("super::{module}::"));
+        last = i;
+    }
+    out.push_str(&main[last..]);
+    out.push_str(&source[body.end..]);
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DAY: &str = r#"pub struct Day3 {
+    input: PathBuf,
+}
+
+pub fn part1(input: &str) -> Result<usize, AocError> {
+    Ok(input.len())
+}
+
+pub fn part2(input: &str) -> Result<usize, AocError> {
+    part1(input)
+}
+
+impl CommandImpl for Day3 {
+    fn main(&self) -> Result<(), AocError> {
+        let string = read_input(&self.input)?;
+        let first = part1(&string)?;
+        println!("part 1: {first}");
+        println!("part 2: {}", part2(&string)?);
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn test_render() {
+        let input = include_str!("../../../examples/day3/example.txt");
+        crate::assert_snapshot!("day3_render", part1(input).unwrap());
+    }
+}
+"#;
+
+    #[test]
+    fn test_fork_day() {
+        let forked = fork_source(DAY, "day3", "day3b").unwrap();
+        assert!(forked.contains("pub struct Day3B {"));
+        assert!(forked.contains("impl CommandImpl for Day3B {"));
+        assert!(forked.contains("let first = super::day3::part1(&string)?;"));
+        assert!(forked.contains("println!(\"part 2: {}\", part2(&string)?);"));
+        // only the main runs part 1 from the original, part 2 keeps its own call
+        assert!(forked.contains("    part1(input)\n"));
+        assert!(forked.contains("examples/day3b/example.txt"));
+        assert!(forked.contains("\"day3b_render\""));
+        assert!(!forked.contains("Day3 "));
+
+        let linked = link_part2(DAY, "day3b").unwrap();
+        assert!(linked.contains("let first = part1(&string)?;"));
+        assert!(linked.contains("super::day3b::part2(&string)?"));
+        assert!(link_part2(&linked, "day3b").is_err());
+        assert!(link_part2("pub fn part2() {}", "day3b").is_err());
+    }
+
+    #[test]
+    fn test_fork_every_day() {
+        for entry in fs::read_dir(commands_dir()).unwrap() {
+            let path = entry.unwrap().path();
+            let day = path.file_stem().unwrap().to_str().unwrap();
+            if day == "mod" {
+                continue;
+            }
+            let source = fs::read_to_string(&path).unwrap();
+            let forked =
+                fork_source(&source, day, "day99").unwrap_or_else(|e| panic!("{day}: {e}"));
+            assert!(forked.contains(&format!("super::{day}::part1(")), "{day}");
+            assert!(link_part2(&source, "day99").is_ok(), "{day}");
+        }
+    }
+}
@@ -0,0 +1,191 @@
+use std::collections::BTreeSet;
+
+use crate::{new_day::Template, AocError};
+
+/// The shape of a puzzle input, as far as it can be told from an example.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Shape {
+    Grid { alphabet: BTreeSet<char> },
+    Records { label: String },
+    Sections,
+    Numbers,
+    Lines,
+}
+
+impl Shape {
+    pub fn template(&self) -> Template {
+        match self {
+            Shape::Grid { .. } => Template::Grid,
+            Shape::Records { .. } => Template::Records,
+            Shape::Sections => Template::Sections,
+            Shape::Numbers => Template::Numbers,
+            Shape::Lines => Template::Lines,
+        }
+    }
+
+    // bake what was observed in the example into the template's parser stubs
+    pub fn render(&self, source: &str) -> Result<String, AocError> {
+        match self {
+            Shape::Grid { alphabet } => {
+                let variants: String =
+                    alphabet.iter().map(|c| format!("    {},\n", tile_name(*c))).collect();
+                let arms: String = alphabet
+                    .iter()
+                    .map(|c| format!("            {c:?} => Ok(Tile::{}),\n", tile_name(*c)))
+                    .collect();
+                let source = replace_snippet(source, "    Open,\n    Wall,\n", &variants)?;
+                let source = replace_snippet(
+                    &source,
+                    "            '.' => Ok(Tile::Open),\n            '#' => Ok(Tile::Wall),\n",
+                    &arms,
+                )?;
+                let one_of = format!("one_of({:?})", alphabet.iter().collect::<String>());
+                replace_snippet(&source, "one_of(\".#\")", &one_of)
+            }
+            Shape::Records { label } => {
+                replace_snippet(source, "labelled(\"Card\"", &format!("labelled({label:?}"))
+            }
+            _ => Ok(source.to_owned()),
+        }
+    }
+}
+
+fn replace_snippet(source: &str, snippet: &str, replacement: &str) -> Result<String, AocError> {
+    if !source.contains(snippet) {
+        return Err(format!("template has no {snippet:?} to replace").into());
+    }
+    Ok(source.replace(snippet, replacement))
+}
+
+pub fn infer_shape(example: &str) -> Shape {
+    // the same clean up as the runner's `utils::normalize`: BOM, CRLF and trailing spaces
+    let example = example.trim_start_matches('\u{feff}').trim_end();
+    let lines: Vec<&str> = example.lines().map(str::trim_end).collect();
+    if lines.is_empty() {
+        return Shape::Lines;
+    }
+
+    let sections: Vec<&[&str]> = lines.split(|l| l.trim().is_empty()).collect();
+    if sections.len() > 1 && sections.iter().all(|s| s.first().is_some_and(|h| h.contains(':'))) {
+        return Shape::Sections;
+    }
+
+    let labels: Vec<Option<&str>> = lines.iter().map(|l| record_label(l)).collect();
+    if let Some(Some(label)) = labels.first() {
+        if labels.iter().all(|l| l == &Some(*label)) {
+            return Shape::Records { label: label.to_string() };
+        }
+    }
+
+    let width = lines[0].chars().count();
+    if lines.len() > 1
+        && width > 1
+        && lines.iter().all(|l| l.chars().count() == width && !l.contains(char::is_whitespace))
+    {
+        return Shape::Grid { alphabet: lines.iter().flat_map(|l| l.chars()).collect() };
+    }
+
+    if lines.iter().all(|l| {
+        let mut numbers = l.split_whitespace().peekable();
+        numbers.peek().is_some() && numbers.all(|n| n.parse::<i64>().is_ok())
+    }) {
+        return Shape::Numbers;
+    }
+
+    Shape::Lines
+}
+
+// `Card 12: 41 48 | 83 86` -> `Card`
+fn record_label(line: &str) -> Option<&str> {
+    let (head, body) = line.split_once(':')?;
+    let mut words = head.split_whitespace();
+    let label = words.next()?;
+    let id_ok = words.next().is_none_or(|id| id.chars().all(|c| c.is_ascii_digit()));
+    let body_ok = body.contains(|c: char| c.is_ascii_digit())
+        && body.chars().all(|c| c.is_ascii_digit() || c == ' ' || c == '|');
+    let label_ok = label.chars().all(|c| c.is_ascii_alphabetic());
+    (label_ok && id_ok && words.next().is_none() && body_ok).then_some(label)
+}
+
+// a variant name for a tile character, `.` -> `Dot`
+fn tile_name(c: char) -> String {
+    let name = match c {
+        '.' => "Dot",
+        '#' => "Hash",
+        '|' => "Pipe",
+        '-' => "Dash",
+        '/' => "Slash",
+        '\\' => "Backslash",
+        '^' => "Caret",
+        '<' => "Less",
+        '>' => "Greater",
+        '@' => "At",
+        '*' => "Star",
+        '+' => "Plus",
+        '~' => "Tilde",
+        '=' => "Equals",
+        '%' => "Percent",
+        '&' => "Ampersand",
+        '$' => "Dollar",
+        '!' => "Bang",
+        '?' => "Question",
+        ',' => "Comma",
+        ':' => "Colon",
+        ';' => "Semicolon",
+        '_' => "Underscore",
+        '(' => "LeftParen",
+        ')' => "RightParen",
+        '[' => "LeftBracket",
+        ']' => "RightBracket",
+        '{' => "LeftBrace",
+        '}' => "RightBrace",
+        _ if c.is_ascii_uppercase() => return c.to_string(),
+        _ if c.is_ascii_lowercase() => return format!("Lower{}", c.to_ascii_uppercase()),
+        _ if c.is_ascii_digit() => return format!("Digit{c}"),
+        _ => return format!("Char{:X}", c as u32),
+    };
+    name.to_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_infer_grid() {
+        let shape = infer_shape("7-F7-\n.FJ|7\nSJLL7\n|F--J\nLJ.LJ\n");
+        assert_eq!(shape, Shape::Grid { alphabet: "-.7FJLS|".chars().collect() });
+        let rendered = shape.render(Template::Grid.source()).unwrap();
+        assert!(rendered.contains("one_of(\"-.7FJLS|\")"));
+        assert!(rendered.contains("    Digit7,\n"));
+        assert!(rendered.contains("            '|' => Ok(Tile::Pipe),\n"));
+    }
+
+    #[test]
+    fn test_infer_ignores_bom_and_crlf() {
+        let example = "\u{feff}-L|F7  \r\n7S-7|\r\nL|7||\r\n-L-J|\r\nL|-JF\r\n";
+        assert_eq!(infer_shape(example), Shape::Grid { alphabet: "-7FJLS|".chars().collect() });
+    }
+
+    #[test]
+    fn test_infer_sections() {
+        let example = "seeds: 79 14 55 13\n\nseed-to-soil map:\n50 98 2\n52 50 48\n";
+        assert_eq!(infer_shape(example), Shape::Sections);
+    }
+
+    #[test]
+    fn test_infer_records() {
+        let example = "Game 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53\n\
+                       Game 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19\n";
+        let shape = infer_shape(example);
+        assert_eq!(shape, Shape::Records { label: "Game".to_owned() });
+        assert!(shape.render(Template::Records.source()).unwrap().contains("labelled(\"Game\""));
+    }
+
+    #[test]
+    fn test_infer_numbers_and_lines() {
+        assert_eq!(infer_shape("0 3 6 9 12 15\n1 3 6 10 15\n-2 4\n"), Shape::Numbers);
+        assert_eq!(infer_shape("two1nine\neightwothree\nabcone2threexyz\n"), Shape::Lines);
+        assert_eq!(infer_shape(""), Shape::Lines);
+    }
+}
@@ -1,16 +1,30 @@
+mod changes;
+mod doctor;
+mod fork_part2;
+mod infer;
+mod new_day;
+mod remove_day;
+mod rename_day;
+mod report;
+mod rewrite;
+
+use aoc_common::error::AocError;
+use doctor::Doctor;
 use enum_dispatch::enum_dispatch;
-use std::env;
+use fork_part2::ForkPart2;
+use new_day::NewDay;
+use remove_day::RemoveDay;
+use rename_day::RenameDay;
+use report::Report;
 use std::path::Path;
-use std::process::Command;
-use std::{error::Error, path::PathBuf};
-
-type DynError = Box<dyn Error>;
+use std::path::PathBuf;
+use std::{fs, process::ExitCode};
 
 use clap::Parser;
 
 #[enum_dispatch]
 trait CommandImpl {
-    fn main(&self) -> Result<(), DynError>;
+    fn main(&self) -> Result<(), AocError>;
 }
 
 #[derive(Parser, Debug)]
@@ -23,43 +37,46 @@ struct Opts {
 #[derive(Parser, Debug)]
 enum SubCommand {
     NewDay(NewDay),
+    ForkPart2(ForkPart2),
+    RemoveDay(RemoveDay),
+    RenameDay(RenameDay),
+    Doctor(Doctor),
+    Report(Report),
 }
-fn main() -> Result<(), DynError> {
+fn main() -> ExitCode {
     let opts = Opts::parse();
 
-    opts.subcommand.main()
+    match opts.subcommand.main() {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("error: {e}");
+            ExitCode::from(e.exit_code())
+        }
+    }
 }
 
-// -------------- Tasks -----------
-
-#[derive(Parser, Debug)]
-struct NewDay {
-    /// Create a new day
-    #[clap(long, short)]
-    name: String,
+fn project_root() -> PathBuf {
+    Path::new(&env!("CARGO_MANIFEST_DIR")).ancestors().nth(1).unwrap().to_path_buf()
 }
 
-impl CommandImpl for NewDay {
-    fn main(&self) -> Result<(), DynError> {
-        let template = project_root().join("aoc").join("src").join("commands").join("day0.rs");
-        let dest = project_root()
-            .join("aoc")
-            .join("src")
-            .join("commands")
-            .join(format!("{}.rs", self.name));
+fn commands_dir() -> PathBuf {
+    project_root().join("aoc").join("src").join("commands")
+}
 
-        let status = Command::new("cp")
-            .current_dir(project_root())
-            .args(&[template.to_str().unwrap(), dest.to_str().unwrap()])
-            .status()?;
-        if !status.success() {
-            return Err("cargo install failed".into());
+// every source file that may refer to a day module by name
+fn source_files() -> Result<Vec<PathBuf>, AocError> {
+    let aoc = project_root().join("aoc");
+    let dirs =
+        [commands_dir(), aoc.join("benches"), aoc.join("tests"), aoc.join("fuzz/fuzz_targets")];
+    let mut files = Vec::new();
+    for dir in dirs.iter().filter(|d| d.is_dir()) {
+        for entry in fs::read_dir(dir)? {
+            let path = entry?.path();
+            if path.extension().is_some_and(|e| e == "rs") {
+                files.push(path);
+            }
         }
-
-        Ok(())
     }
-}
-
-fn project_root() -> PathBuf {
-    Path::new(&env!("CARGO_MANIFEST_DIR")).ancestors().nth(1).unwrap().to_path_buf()
+    files.sort();
+    Ok(files)
 }
@@ -0,0 +1,265 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use aoc_common::text::normalize;
+use clap::{Parser, ValueEnum};
+
+use crate::{infer::infer_shape, project_root, AocError, CommandImpl};
+
+#[derive(Parser, Debug)]
+pub struct NewDay {
+    /// Create a new day
+    #[clap(long, short)]
+    name: String,
+    /// Shape of the puzzle input the day starts from, `lines` unless inferred from an example
+    #[clap(long, short, value_enum)]
+    template: Option<Template>,
+    /// Example input to pick the template from and to record as the day's example
+    #[clap(long)]
+    from_example: Option<PathBuf>,
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Template {
+    /// A rectangular field of characters, like day10
+    Grid,
+    /// One record per line, like day1
+    Lines,
+    /// Blank line separated sections with a `name:` header, like the day5 almanac
+    Sections,
+    /// Whitespace separated numbers on every line
+    Numbers,
+    /// `Label N: numbers | numbers` on every line, like the day5 cards
+    Records,
+}
+
+impl Template {
+    pub fn source(&self) -> &'static str {
+        match self {
+            Template::Grid => include_str!("../templates/grid.rs"),
+            Template::Lines => include_str!("../templates/lines.rs"),
+            Template::Sections => include_str!("../templates/sections.rs"),
+            Template::Numbers => include_str!("../templates/numbers.rs"),
+            Template::Records => include_str!("../templates/records.rs"),
+        }
+    }
+}
+
+impl CommandImpl for NewDay {
+    fn main(&self) -> Result<(), AocError> {
+        let root = project_root();
+        for path in self.scaffold(&root)? {
+            println!("wrote {}", path.strip_prefix(&root).unwrap_or(&path).display());
+        }
+        Ok(())
+    }
+}
+
+impl NewDay {
+    // write the day's module, examples and empty input under `root`, returning what was written
+    fn scaffold(&self, root: &Path) -> Result<Vec<PathBuf>, AocError> {
+        validate_day_name(&self.name)?;
+        let commands = root.join("aoc").join("src").join("commands");
+        let dest = commands.join(format!("{}.rs", self.name));
+        let examples = root.join("examples").join(&self.name);
+        let input = root.join("input").join(format!("{}.txt", self.name));
+        for path in [&dest, &examples, &input] {
+            if path.exists() {
+                return Err(format!("{} already exists", path.display()).into());
+            }
+        }
+
+        // the scaffolded tests `include_str!` the example as is, without `read_input`
+        let example = match &self.from_example {
+            Some(path) => normalize(&fs::read_to_string(path)?).into_owned(),
+            None => String::new(),
+        };
+        let source = match (self.template, &self.from_example) {
+            (Some(template), _) => template.source().to_owned(),
+            (None, Some(_)) => {
+                let shape = infer_shape(&example);
+                println!("using the {:?} template for {shape:?}", shape.template());
+                shape.render(shape.template().source())?
+            }
+            (None, None) => Template::Lines.source().to_owned(),
+        };
+        let mod_rs = fs::read_to_string(commands.join("mod.rs"))?;
+        let registered = register_day(&mod_rs, &self.name)?;
+
+        fs::write(&dest, render_template(&source, &self.name))?;
+        fs::write(commands.join("mod.rs"), registered)?;
+        fs::create_dir_all(&examples)?;
+        fs::write(examples.join("example.txt"), example)?;
+        fs::write(examples.join("example.answers"), "# part1: \n# part2: \n")?;
+        fs::create_dir_all(input.parent().unwrap())?;
+        fs::write(&input, "")?;
+        Ok(vec![dest, commands.join("mod.rs"), examples, input])
+    }
+}
+
+// `day7`, `day7b` or `day5part1`, the module name of the day
+pub fn validate_day_name(name: &str) -> Result<(), AocError> {
+    let valid = name.strip_prefix("day").is_some_and(|rest| {
+        rest.starts_with(|c: char| c.is_ascii_digit())
+            && rest.chars().all(|c| c.is_ascii_digit() || c.is_ascii_lowercase())
+    });
+    if valid {
+        Ok(())
+    } else {
+        Err(format!("{name:?} is not a day name like day7 or day7b").into())
+    }
+}
+
+// `day7` -> `Day7`
+// the first letter and the one after each run of digits are uppercased, `day5part1` -> `Day5Part1`
+pub fn struct_name(name: &str) -> String {
+    let mut after_digit = true;
+    name.chars()
+        .map(|c| {
+            let upper = if after_digit { c.to_ascii_uppercase() } else { c };
+            after_digit = c.is_ascii_digit();
+            upper
+        })
+        .collect()
+}
+
+fn render_template(template: &str, name: &str) -> String {
+    template.replace("Day0", &struct_name(name)).replace("day0", name)
+}
+
+// add `pub mod <name>;` and the `SubCommand` variant, keeping both lists sorted
+pub fn register_day(mod_rs: &str, name: &str) -> Result<String, AocError> {
+    let module = format!("pub mod {name};");
+    let variant = format!("    {}({name}::{}),", struct_name(name), struct_name(name));
+    let mut lines: Vec<String> = mod_rs.lines().map(str::to_owned).collect();
+    if lines.contains(&module) {
+        return Err(format!("{name} is already declared in commands/mod.rs").into());
+    }
+
+    let modules = lines.iter().position(|l| l.starts_with("pub mod ")).unwrap_or(0);
+    lines.insert(modules, module);
+    let end = modules + lines[modules..].iter().take_while(|l| l.starts_with("pub mod ")).count();
+    lines[modules..end].sort_by_key(|l| l.trim_end_matches(';').to_owned());
+
+    let Some(enum_start) = lines.iter().position(|l| l.starts_with("pub enum SubCommand")) else {
+        return Err("no SubCommand enum in commands/mod.rs".into());
+    };
+    let first = enum_start + 1;
+    let Some(len) = lines[first..].iter().position(|l| l.starts_with('}')) else {
+        return Err("unterminated SubCommand enum in commands/mod.rs".into());
+    };
+    lines.insert(first, variant);
+    lines[first..=first + len].sort();
+
+    Ok(lines.join("\n") + "\n")
+}
+
+// drop the `pub mod <name>;` line and the `SubCommand` variant of a day
+pub fn unregister_day(mod_rs: &str, name: &str) -> Result<String, AocError> {
+    let module = format!("pub mod {name};");
+    let variant = format!("    {}({name}::{}),", struct_name(name), struct_name(name));
+    let lines: Vec<&str> = mod_rs.lines().collect();
+    for line in [&module, &variant] {
+        if !lines.contains(&line.as_str()) {
+            return Err(format!("`{}` is not in commands/mod.rs", line.trim()).into());
+        }
+    }
+    let kept: Vec<&str> = lines.into_iter().filter(|l| *l != module && *l != variant).collect();
+    Ok(kept.join("\n") + "\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MOD_RS: &str = "pub mod day1;
+pub mod day10;
+
+#[enum_dispatch(CommandImpl)]
+#[derive(Parser, Debug)]
+pub enum SubCommand {
+    Day1(day1::Day1),
+    Day10(day10::Day10),
+}
+";
+
+    #[test]
+    fn test_register_day() {
+        let registered = register_day(MOD_RS, "day7").unwrap();
+        assert_eq!(
+            registered,
+            "pub mod day1;
+pub mod day10;
+pub mod day7;
+
+#[enum_dispatch(CommandImpl)]
+#[derive(Parser, Debug)]
+pub enum SubCommand {
+    Day1(day1::Day1),
+    Day10(day10::Day10),
+    Day7(day7::Day7),
+}
+"
+        );
+        assert!(register_day(&registered, "day7").is_err());
+        assert_eq!(unregister_day(&registered, "day7").unwrap(), MOD_RS);
+        assert!(unregister_day(MOD_RS, "day7").is_err());
+    }
+
+    #[test]
+    fn test_struct_name() {
+        assert_eq!(struct_name("day1"), "Day1");
+        assert_eq!(struct_name("day10"), "Day10");
+        assert_eq!(struct_name("day5part1"), "Day5Part1");
+        assert_eq!(struct_name("day7b"), "Day7B");
+    }
+
+    #[test]
+    fn test_render_template() {
+        let rendered = render_template("pub struct Day0;\n\"examples/day0/example.txt\"", "day7b");
+        assert_eq!(rendered, "pub struct Day7B;\n\"examples/day7b/example.txt\"");
+    }
+
+    #[test]
+    fn test_templates_render() {
+        for template in Template::value_variants() {
+            let rendered = render_template(template.source(), "day7");
+            assert!(rendered.contains("pub struct Day7 {"));
+            assert!(rendered.contains("examples/day7/example.txt"));
+            assert!(!rendered.contains("day0") && !rendered.contains("Day0"));
+            // doctor rejects a todo!() in tests, a fresh day has to pass it
+            let tests = rendered.split("#[cfg(test)]").nth(1).unwrap();
+            assert!(!tests.contains("todo!("));
+        }
+    }
+
+    #[test]
+    fn test_scaffold_normalizes_the_example() {
+        let root = std::env::temp_dir().join(format!("xtask-new-day-{}", std::process::id()));
+        fs::create_dir_all(root.join("aoc/src/commands")).unwrap();
+        fs::write(root.join("aoc/src/commands/mod.rs"), MOD_RS).unwrap();
+        let sample = root.join("sample.txt");
+        fs::write(&sample, "\u{feff}-L|F7  \r\n7S-7|\r\nL|7||\r\n").unwrap();
+
+        let new_day =
+            NewDay { name: "day7".to_owned(), template: None, from_example: Some(sample) };
+        new_day.scaffold(&root).unwrap();
+        let example = fs::read_to_string(root.join("examples/day7/example.txt")).unwrap();
+        assert_eq!(example, "-L|F7\n7S-7|\nL|7||\n");
+        let source = fs::read_to_string(root.join("aoc/src/commands/day7.rs")).unwrap();
+        assert!(source.contains("one_of(\"-7FLS|\")"));
+        assert!(new_day.scaffold(&root).is_err());
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn test_validate_day_name() {
+        assert!(validate_day_name("day7").is_ok());
+        assert!(validate_day_name("day5part1").is_ok());
+        assert!(validate_day_name("day").is_err());
+        assert!(validate_day_name("Day7").is_err());
+        assert!(validate_day_name("../day7").is_err());
+    }
+}
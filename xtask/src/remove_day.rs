@@ -0,0 +1,185 @@
+use std::fs;
+
+use clap::Parser;
+
+use crate::{
+    changes::ChangeSet,
+    commands_dir,
+    new_day::{unregister_day, validate_day_name},
+    project_root,
+    rewrite::contains_ident_part,
+    source_files, AocError, CommandImpl,
+};
+
+#[derive(Parser, Debug)]
+pub struct RemoveDay {
+    /// Day to delete along with its examples and snapshots
+    #[clap(long, short)]
+    name: String,
+    /// Print the changes without applying them
+    #[clap(long)]
+    dry_run: bool,
+}
+
+impl CommandImpl for RemoveDay {
+    fn main(&self) -> Result<(), AocError> {
+        validate_day_name(&self.name)?;
+        let root = project_root();
+        let commands = commands_dir();
+        let module = commands.join(format!("{}.rs", self.name));
+        if !module.exists() {
+            return Err(format!("{} does not exist", module.display()).into());
+        }
+
+        let mut changes = ChangeSet::default();
+        let mod_rs = commands.join("mod.rs");
+        changes.write(&mod_rs, unregister_day(&fs::read_to_string(&mod_rs)?, &self.name)?)?;
+        changes.remove(&module)?;
+
+        // the day's own fuzz targets go with it, along with their `[[bin]]` entries and corpora
+        let fuzz = root.join("aoc").join("fuzz");
+        let prefix = format!("{}_", self.name);
+        let mut users = Vec::new();
+        for path in source_files()?.into_iter().filter(|p| *p != module && *p != mod_rs) {
+            let stem = path.file_stem().and_then(|s| s.to_str()).unwrap_or_default();
+            if path.starts_with(fuzz.join("fuzz_targets")) && stem.starts_with(&prefix) {
+                changes.remove(&path)?;
+                let manifest = fuzz.join("Cargo.toml");
+                let source = changes.read(&manifest)?.unwrap_or_default();
+                changes.write(&manifest, remove_bin(&source, stem))?;
+                for dir in ["corpus", "artifacts"].map(|dir| fuzz.join(dir).join(stem)) {
+                    if dir.is_dir() {
+                        changes.delete_dir(&dir);
+                    }
+                }
+                continue;
+            }
+            let source = fs::read_to_string(&path)?;
+            let source = if path.starts_with(root.join("aoc").join("benches")) {
+                let stripped = remove_items(&source, &self.name);
+                changes.write(&path, stripped.clone())?;
+                stripped
+            } else {
+                source
+            };
+            // whatever is left is code of another day that has to be changed by hand
+            if contains_ident_part(&source, &self.name) {
+                users.push(path.strip_prefix(&root).unwrap_or(&path).display().to_string());
+            }
+        }
+        if !users.is_empty() {
+            return Err(format!("{} is still used by {}", self.name, users.join(", ")).into());
+        }
+
+        changes.remove_dir(&root.join("examples").join(&self.name))?;
+        let snapshots = root.join("snapshots");
+        if snapshots.is_dir() {
+            for snapshot in fs::read_dir(&snapshots)? {
+                let path = snapshot?.path();
+                let stem = path.file_stem().and_then(|s| s.to_str()).unwrap_or_default();
+                if stem.starts_with(&prefix) {
+                    changes.remove(&path)?;
+                }
+            }
+        }
+
+        print!("{}", changes.render(&root));
+        let input = root.join("input").join(format!("{}.txt", self.name));
+        if input.exists() {
+            println!("kept {}", input.strip_prefix(&root).unwrap_or(&input).display());
+        }
+        if !self.dry_run {
+            changes.apply()?;
+        }
+        Ok(())
+    }
+}
+
+// the fuzz manifest without the `[[bin]]` entry of `target`
+fn remove_bin(manifest: &str, target: &str) -> String {
+    let name = format!("name = \"{target}\"");
+    let sections: Vec<&str> = manifest
+        .trim_end()
+        .split("\n\n")
+        .filter(|section| !(section.starts_with("[[bin]]") && section.contains(&name)))
+        .collect();
+    sections.join("\n\n") + "\n"
+}
+
+// a bench file without the day's functions and their comments, and without the day in the
+// `use` and `criterion_group!` lists
+fn remove_items(source: &str, day: &str) -> String {
+    let lines: Vec<&str> = source.lines().collect();
+    let mut kept: Vec<String> = Vec::new();
+    let mut i = 0;
+    while i < lines.len() {
+        let name = lines[i].strip_prefix("fn ").and_then(|rest| rest.split('(').next());
+        if name.is_some_and(|name| contains_ident_part(name, day)) {
+            while kept.last().is_some_and(|line| line.starts_with("//")) {
+                kept.pop();
+            }
+            i += lines[i..].iter().position(|line| *line == "}").map_or(lines.len(), |end| end + 1);
+            if lines.get(i).is_some_and(|line| line.is_empty()) {
+                i += 1;
+            }
+            continue;
+        }
+        kept.push(remove_from_list(lines[i], day));
+        i += 1;
+    }
+    kept.join("\n") + "\n"
+}
+
+// `{day1, day10}` or `(benches, bench_day1)` without the entries naming the day, other lines
+// as they are
+fn remove_from_list(line: &str, day: &str) -> String {
+    let (Some(open), Some(close)) = (line.find(['{', '(']), line.rfind(['}', ')'])) else {
+        return line.to_owned();
+    };
+    if close < open {
+        return line.to_owned();
+    }
+    let is_ident = |item: &&str| item.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+    let items: Vec<&str> = line[open + 1..close].split(',').map(str::trim).collect();
+    if !items.iter().all(is_ident) || !items.iter().any(|item| contains_ident_part(item, day)) {
+        return line.to_owned();
+    }
+    let kept: Vec<&str> =
+        items.into_iter().filter(|item| !contains_ident_part(item, day)).collect();
+    format!("{}{}{}", &line[..=open], kept.join(", "), &line[close..])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_remove_items() {
+        let bench = "use aoc::{\n    commands::{day1, day10},\n};\n\n\
+                     // generated input\nfn day1_input() -> String {\n    String::new()\n}\n\n\
+                     fn bench_day10(c: &mut Criterion) {\n    day10::part1(\"\").unwrap();\n}\n\n\
+                     criterion_group!(benches, bench_day1, bench_day10);\n";
+        assert_eq!(
+            remove_items(bench, "day1"),
+            "use aoc::{\n    commands::{day10},\n};\n\n\
+             fn bench_day10(c: &mut Criterion) {\n    day10::part1(\"\").unwrap();\n}\n\n\
+             criterion_group!(benches, bench_day10);\n"
+        );
+        assert_eq!(remove_items(bench, "day7"), bench);
+    }
+
+    #[test]
+    fn test_remove_bin() {
+        let manifest =
+            "[package]\nname = \"aoc-fuzz\"\n\n[[bin]]\nname = \"day1_x\"\ntest = false\n\n\
+                        [[bin]]\nname = \"day10_y\"\ntest = false\n";
+        assert_eq!(
+            remove_bin(manifest, "day1_x"),
+            "[package]\nname = \"aoc-fuzz\"\n\n[[bin]]\nname = \"day10_y\"\ntest = false\n"
+        );
+        assert_eq!(
+            remove_bin(manifest, "day10_y"),
+            "[package]\nname = \"aoc-fuzz\"\n\n[[bin]]\nname = \"day1_x\"\ntest = false\n"
+        );
+    }
+}
@@ -0,0 +1,139 @@
+use std::fs;
+
+use clap::Parser;
+
+use crate::{
+    changes::ChangeSet,
+    commands_dir,
+    new_day::{register_day, struct_name, unregister_day, validate_day_name},
+    project_root,
+    rewrite::{contains_ident_part, replace_ident_part, replace_word},
+    source_files, AocError, CommandImpl,
+};
+
+#[derive(Parser, Debug)]
+pub struct RenameDay {
+    /// Day to rename
+    #[clap(long, short)]
+    name: String,
+    /// New module name for the day
+    #[clap(long, short)]
+    to: String,
+    /// Print the changes without applying them
+    #[clap(long)]
+    dry_run: bool,
+}
+
+impl CommandImpl for RenameDay {
+    fn main(&self) -> Result<(), AocError> {
+        validate_day_name(&self.name)?;
+        validate_day_name(&self.to)?;
+        let root = project_root();
+        let commands = commands_dir();
+        let module = commands.join(format!("{}.rs", self.name));
+        let dest = commands.join(format!("{}.rs", self.to));
+        if !module.exists() {
+            return Err(format!("{} does not exist", module.display()).into());
+        }
+        if dest.exists() {
+            return Err(format!("{} already exists", dest.display()).into());
+        }
+
+        let mut changes = ChangeSet::default();
+        let mod_rs = commands.join("mod.rs");
+        let registered = unregister_day(&fs::read_to_string(&mod_rs)?, &self.name)?;
+        changes.write(&mod_rs, register_day(&registered, &self.to)?)?;
+        changes.rename(&module, &dest)?;
+        for path in source_files()?.into_iter().filter(|p| *p != module && *p != mod_rs) {
+            let source = fs::read_to_string(&path)?;
+            if contains_ident_part(&source, &self.name) {
+                changes.write(&path, rename_in_source(&source, &self.name, &self.to))?;
+            }
+        }
+        let source = changes.read(&dest)?.unwrap_or_default();
+        changes.write(&dest, rename_in_source(&source, &self.name, &self.to))?;
+
+        // fuzz targets named after the day, their `[[bin]]` entries and corpora
+        let fuzz = root.join("aoc").join("fuzz");
+        let fuzz_toml = fuzz.join("Cargo.toml");
+        if fuzz_toml.exists() {
+            let manifest = fs::read_to_string(&fuzz_toml)?;
+            changes.write(&fuzz_toml, replace_ident_part(&manifest, &self.name, &self.to))?;
+        }
+        for dir in ["fuzz_targets", "corpus", "artifacts"] {
+            let dir = fuzz.join(dir);
+            if !dir.is_dir() {
+                continue;
+            }
+            for entry in fs::read_dir(&dir)? {
+                let path = entry?.path();
+                let Some(file_name) = path.file_name().and_then(|s| s.to_str()) else {
+                    continue;
+                };
+                let Some(rest) = file_name.strip_prefix(&format!("{}_", self.name)) else {
+                    continue;
+                };
+                let dest = dir.join(format!("{}_{rest}", self.to));
+                // the targets were rewritten with the other sources above, corpora are moved as is
+                if path.is_dir() {
+                    changes.move_dir(&path, &dest)?;
+                } else {
+                    changes.rename(&path, &dest)?;
+                }
+            }
+        }
+
+        let examples = root.join("examples");
+        changes.rename_dir(&examples.join(&self.name), &examples.join(&self.to))?;
+        for dir in ["snapshots", "input"] {
+            let dir = root.join(dir);
+            if !dir.is_dir() {
+                continue;
+            }
+            for entry in fs::read_dir(&dir)? {
+                let path = entry?.path();
+                let Some(file_name) = path.file_name().and_then(|s| s.to_str()) else {
+                    continue;
+                };
+                // `day5_stages.snap` in snapshots, `day5.txt` in input
+                if let Some(rest) = file_name.strip_prefix(&self.name) {
+                    if rest.starts_with(['_', '.']) {
+                        changes.rename(&path, &dir.join(format!("{}{rest}", self.to)))?;
+                    }
+                }
+            }
+        }
+
+        print!("{}", changes.render(&root));
+        if !self.dry_run {
+            changes.apply()?;
+        }
+        Ok(())
+    }
+}
+
+// the struct, module path, example paths, snapshot names and `day5_`/`_day5` identifiers of a day
+pub fn rename_in_source(source: &str, from: &str, to: &str) -> String {
+    let renamed = replace_word(source, &struct_name(from), &struct_name(to));
+    replace_ident_part(&renamed, from, to)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rename_in_source() {
+        let source = "pub struct Day5;\nimpl CommandImpl for Day5 {}\n\
+                      include_str!(\"../../../examples/day5/almanac.txt\");\n\
+                      assert_snapshot!(\"day5_stages\", x);\nuse super::day5part1;\n\
+                      fn bench_day5() { let day5_path = 1; }\n";
+        assert_eq!(
+            rename_in_source(source, "day5", "day6"),
+            "pub struct Day6;\nimpl CommandImpl for Day6 {}\n\
+             include_str!(\"../../../examples/day6/almanac.txt\");\n\
+             assert_snapshot!(\"day6_stages\", x);\nuse super::day5part1;\n\
+             fn bench_day6() { let day6_path = 1; }\n"
+        );
+    }
+}
@@ -0,0 +1,240 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
+    time::{Duration, Instant},
+};
+
+use clap::Parser;
+
+use crate::{commands_dir, doctor::registrations, project_root, AocError, CommandImpl};
+
+const START: &str = "<!-- report:start -->";
+const END: &str = "<!-- report:end -->";
+
+#[derive(Parser, Debug)]
+pub struct Report {
+    /// Print the table instead of writing it into the README
+    #[clap(long)]
+    print: bool,
+}
+
+/// How a part's answer compares with the one recorded in `input/<day>.answers`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Status {
+    Correct,
+    Wrong,
+    Unverified,
+    Unsolved,
+}
+
+impl Status {
+    fn symbol(self) -> &'static str {
+        match self {
+            Status::Correct => "✓",
+            Status::Wrong => "✗",
+            Status::Unverified => "?",
+            Status::Unsolved => "–",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Row {
+    pub day: String,
+    pub parts: [Status; 2],
+    pub runtime: Option<Duration>,
+    pub lines: usize,
+}
+
+impl Row {
+    fn stars(&self) -> usize {
+        self.parts.iter().filter(|p| **p == Status::Correct).count()
+    }
+}
+
+// the subcommand clap derives from a `SubCommand` variant, `Day5Part1` -> `day5-part1`
+fn subcommand(variant: &str) -> String {
+    let mut name = String::new();
+    for (i, c) in variant.chars().enumerate() {
+        if i > 0 && c.is_ascii_uppercase() {
+            name.push('-');
+        }
+        name.push(c.to_ascii_lowercase());
+    }
+    name
+}
+
+// `part 1: 35` from the runner or `part1: 35` from an answers file
+fn answer(text: &str, part: usize) -> Option<&str> {
+    text.lines().find_map(|line| {
+        let rest = line.strip_prefix("part")?.trim_start().strip_prefix(&part.to_string())?;
+        Some(rest.strip_prefix(':')?.trim())
+    })
+}
+
+fn status(output: Option<&str>, recorded: Option<&str>, part: usize) -> Status {
+    match (output.and_then(|o| answer(o, part)), recorded.and_then(|r| answer(r, part))) {
+        (None, _) => Status::Unsolved,
+        (Some(_), None) => Status::Unverified,
+        (Some(actual), Some(expected)) if actual == expected => Status::Correct,
+        (Some(_), Some(_)) => Status::Wrong,
+    }
+}
+
+// lines of solution code, leaving out blank lines, comments and tests
+fn lines_of_code(source: &str) -> usize {
+    let solution = source.split("#[cfg(test)]").next().unwrap_or_default();
+    solution.lines().map(str::trim).filter(|l| !l.is_empty() && !l.starts_with("//")).count()
+}
+
+// the `aoc` binary among cargo's `--message-format=json` messages, wherever the target
+// directory is
+pub fn built_binary(messages: &str) -> Option<PathBuf> {
+    messages.lines().find_map(|line| {
+        let message: serde_json::Value = serde_json::from_str(line).ok()?;
+        if message["reason"] != "compiler-artifact" || message["target"]["name"] != "aoc" {
+            return None;
+        }
+        message["executable"].as_str().map(PathBuf::from)
+    })
+}
+
+fn run_day(root: &Path, binary: &Path, variant: &str, day: &str) -> Result<Row, AocError> {
+    let source = fs::read_to_string(commands_dir().join(format!("{day}.rs")))?;
+    let input = root.join("input").join(format!("{day}.txt"));
+    let recorded = fs::read_to_string(input.with_extension("answers")).ok();
+    let (output, runtime) = if input.exists() {
+        let start = Instant::now();
+        let run =
+            Command::new(binary).arg(subcommand(variant)).arg("--input").arg(&input).output()?;
+        let runtime = start.elapsed();
+        // a failing part 2 still leaves part 1 on stdout
+        (Some(String::from_utf8_lossy(&run.stdout).into_owned()), Some(runtime))
+    } else {
+        (None, None)
+    };
+    let parts = [1, 2].map(|part| status(output.as_deref(), recorded.as_deref(), part));
+    Ok(Row { day: day.to_owned(), parts, runtime, lines: lines_of_code(&source) })
+}
+
+pub fn render_table(rows: &[Row]) -> String {
+    let mut table = String::from(
+        "| Day | Part 1 | Part 2 | Stars | Runtime | Lines |\n|-----|--------|--------|-------|---------|-------|\n",
+    );
+    for row in rows {
+        let runtime = row.runtime.map_or("no input".to_owned(), |r| format!("{r:.1?}"));
+        table.push_str(&format!(
+            "| {} | {} | {} | {} | {runtime} | {} |\n",
+            row.day,
+            row.parts[0].symbol(),
+            row.parts[1].symbol(),
+            "★".repeat(row.stars()),
+            row.lines,
+        ));
+    }
+    let total: Duration = rows.iter().filter_map(|r| r.runtime).sum();
+    let stars: usize = rows.iter().map(Row::stars).sum();
+    table.push_str(&format!("\n{stars} stars, total runtime {total:.1?}\n"));
+    table
+}
+
+// replace the text between the report markers, adding them before the first section if missing
+pub fn splice_report(readme: &str, table: &str) -> String {
+    let section = format!("{START}\n{table}{END}");
+    match (readme.find(START), readme.find(END)) {
+        (Some(start), Some(end)) if start < end => {
+            format!("{}{section}{}", &readme[..start], &readme[end + END.len()..])
+        }
+        _ => match readme.find("\n## ") {
+            Some(i) => format!("{}## Calendar\n\n{section}\n\n{}", &readme[..=i], &readme[i + 1..]),
+            None => format!("{readme}\n## Calendar\n\n{section}\n"),
+        },
+    }
+}
+
+impl CommandImpl for Report {
+    fn main(&self) -> Result<(), AocError> {
+        let root = project_root();
+        let build = Command::new(env!("CARGO"))
+            .args(["build", "--release", "--package", "aoc", "--bin", "aoc"])
+            .arg("--message-format=json-render-diagnostics")
+            .current_dir(&root)
+            .stderr(Stdio::inherit())
+            .output()?;
+        if !build.status.success() {
+            return Err("building aoc in release mode failed".into());
+        }
+        let Some(binary) = built_binary(&String::from_utf8_lossy(&build.stdout)) else {
+            return Err("cargo did not report the aoc binary it built".into());
+        };
+
+        let mod_rs = fs::read_to_string(commands_dir().join("mod.rs"))?;
+        let (_, mut days) = registrations(&mod_rs);
+        // day2 before day10
+        days.sort_by_key(|(_, day)| {
+            let digits: String = day[3..].chars().take_while(char::is_ascii_digit).collect();
+            (digits.parse::<u32>().unwrap_or_default(), day.to_string())
+        });
+        let rows = days
+            .iter()
+            .map(|(variant, day)| run_day(&root, &binary, variant, day))
+            .collect::<Result<Vec<_>, _>>()?;
+        let table = render_table(&rows);
+
+        if self.print {
+            print!("{table}");
+        } else {
+            let readme = root.join("README.md");
+            fs::write(&readme, splice_report(&fs::read_to_string(&readme)?, &table))?;
+            println!("updated {}", readme.display());
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_status() {
+        let output = "part 1: 35\npart 2: 46\n";
+        assert_eq!(subcommand("Day5Part1"), "day5-part1");
+        assert_eq!(status(Some(output), Some("part1: 35\npart2: 47\n"), 1), Status::Correct);
+        assert_eq!(status(Some(output), Some("part1: 35\npart2: 47\n"), 2), Status::Wrong);
+        assert_eq!(status(Some(output), None, 2), Status::Unverified);
+        assert_eq!(status(Some("part 1: 35\n"), None, 2), Status::Unsolved);
+        assert_eq!(status(None, Some("part1: 35\n"), 1), Status::Unsolved);
+    }
+
+    #[test]
+    fn test_render_and_splice() {
+        let rows = [Row {
+            day: "day5".to_owned(),
+            parts: [Status::Correct, Status::Unverified],
+            runtime: Some(Duration::from_millis(3)),
+            lines: 200,
+        }];
+        let table = render_table(&rows);
+        assert!(table.contains("| day5 | ✓ | ? | ★ | 3.0ms | 200 |\n"));
+        assert!(table.ends_with("1 stars, total runtime 3.0ms\n"));
+
+        let readme = "# AOC\n\nintro\n\n## Running\n";
+        let spliced = splice_report(readme, "old\n");
+        assert_eq!(
+            spliced,
+            "# AOC\n\nintro\n\n## Calendar\n\n<!-- report:start -->\nold\n<!-- report:end -->\n\n## Running\n"
+        );
+        assert_eq!(splice_report(&spliced, &table), splice_report(readme, &table));
+    }
+
+    #[test]
+    fn test_built_binary() {
+        let messages = r#"{"reason":"compiler-artifact","target":{"name":"aoc","kind":["lib"]},"executable":null}
+{"reason":"compiler-artifact","target":{"name":"aoc","kind":["bin"]},"executable":"/tmp/target/release/aoc"}
+{"reason":"build-finished","success":true}"#;
+        assert_eq!(built_binary(messages), Some(PathBuf::from("/tmp/target/release/aoc")));
+        assert_eq!(built_binary(r#"{"reason":"build-finished","success":false}"#), None);
+    }
+}
@@ -0,0 +1,79 @@
+// byte offsets where `word` appears as a whole identifier, so `day1` skips `day10` and `day1_x`
+pub fn word_matches(text: &str, word: &str) -> Vec<usize> {
+    let is_ident = |c: char| c.is_ascii_alphanumeric() || c == '_';
+    text.match_indices(word)
+        .map(|(i, _)| i)
+        .filter(|&i| {
+            let before = text[..i].chars().last();
+            let after = text[i + word.len()..].chars().next();
+            !before.is_some_and(is_ident) && !after.is_some_and(is_ident)
+        })
+        .collect()
+}
+
+pub fn replace_word(text: &str, word: &str, with: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut last = 0;
+    for i in word_matches(text, word) {
+        out.push_str(&text[last..i]);
+        out.push_str(with);
+        last = i + word.len();
+    }
+    out.push_str(&text[last..]);
+    out
+}
+
+// whether `word` is one of the `_` separated parts of an identifier, like `day10` in
+// `bench_day10` or `day10_parse_field`
+pub fn contains_ident_part(text: &str, word: &str) -> bool {
+    replace_ident_part(text, word, "") != text
+}
+
+// `word` replaced wherever it is an `_` separated part of an identifier, whole identifiers
+// included
+pub fn replace_ident_part(text: &str, word: &str, with: &str) -> String {
+    let is_ident = |c: char| c.is_ascii_alphanumeric() || c == '_';
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find(is_ident) {
+        out.push_str(&rest[..start]);
+        let len = rest[start..].find(|c: char| !is_ident(c)).unwrap_or(rest.len() - start);
+        let ident = &rest[start..start + len];
+        let parts: Vec<&str> =
+            ident.split('_').map(|part| if part == word { with } else { part }).collect();
+        out.push_str(&parts.join("_"));
+        rest = &rest[start + len..];
+    }
+    out.push_str(rest);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_replace_word() {
+        assert_eq!(replace_word("day1 day10 day1::x", "day1", "day1b"), "day1b day10 day1b::x");
+        assert_eq!(replace_word("Day1(day1::Day1)", "Day1", "Day1b"), "Day1b(day1::Day1b)");
+        assert_eq!(
+            replace_word("\"examples/day5/a.txt\" day5_stages", "day5", "day5b"),
+            "\"examples/day5b/a.txt\" day5_stages"
+        );
+    }
+
+    #[test]
+    fn test_replace_ident_part() {
+        assert_eq!(
+            replace_ident_part(
+                "fn bench_day10() { let day10_path = \"day10_parse_field.rs\"; day10::x(day100) }",
+                "day10",
+                "day9"
+            ),
+            "fn bench_day9() { let day9_path = \"day9_parse_field.rs\"; day9::x(day100) }"
+        );
+        assert_eq!(replace_ident_part("day5part1 day5b_x", "day5", "day6"), "day5part1 day5b_x");
+        assert!(contains_ident_part("[[bin]]\nname = \"day10_parse_field\"", "day10"));
+        assert!(!contains_ident_part("day100 day1b", "day10"));
+    }
+}
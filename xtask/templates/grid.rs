@@ -0,0 +1,94 @@
+use std::path::PathBuf;
+
+use clap::Parser;
+
+use super::CommandImpl;
+use crate::error::AocError;
+
+use crate::utils::{grid::Grid, parse::complete, read_input};
+use nom::{
+    character::complete::{newline, one_of},
+    combinator::{map_res, verify},
+    multi::{many1, separated_list1},
+    IResult,
+};
+
+#[derive(Parser, Debug)]
+pub struct Day0 {
+    #[clap(long, short)]
+    input: PathBuf,
+}
+
+#[derive(Debug, Eq, PartialEq, Hash, Copy, Clone)]
+pub enum Tile {
+    Open,
+    Wall,
+}
+
+impl TryFrom<char> for Tile {
+    type Error = AocError;
+
+    fn try_from(c: char) -> Result<Self, Self::Error> {
+        match c {
+            '.' => Ok(Tile::Open),
+            '#' => Ok(Tile::Wall),
+            _ => Err(AocError::parse(format!("unexpected tile {c:?}"))),
+        }
+    }
+}
+
+fn parse_row(input: &str) -> IResult<&str, Vec<Tile>> {
+    map_res(many1(one_of(".#")), |sequence: Vec<char>| {
+        sequence.into_iter().map(Tile::try_from).collect::<Result<Vec<Tile>, AocError>>()
+    })(input)
+}
+
+// rows must all have the same width
+pub fn parse_field(input: &str) -> IResult<&str, Vec<Vec<Tile>>> {
+    verify(separated_list1(newline, parse_row), |field: &Vec<Vec<Tile>>| {
+        field.iter().all(|row| row.len() == field[0].len())
+    })(input)
+}
+
+// the whole input has to be the field, leftovers are reported where they start
+pub fn parse(input: &str) -> Result<Grid<Tile>, AocError> {
+    Grid::from_rows(complete(input, parse_field)?)
+}
+
+pub fn part1(input: &str) -> Result<usize, AocError> {
+    let field = parse(input)?;
+    todo!("solve part 1 for {} rows", field.height())
+}
+
+pub fn part2(input: &str) -> Result<usize, AocError> {
+    let field = parse(input)?;
+    todo!("solve part 2 for {} rows", field.height())
+}
+
+impl CommandImpl for Day0 {
+    fn main(&self) -> Result<(), AocError> {
+        let string = read_input(&self.input)?;
+        println!("part 1: {}", part1(&string)?);
+        println!("part 2: {}", part2(&string)?);
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../../../examples/day0/example.txt");
+
+    // only the shape for now, check the parsed values once the puzzle is understood
+    #[test]
+    fn test_parse() {
+        if EXAMPLE.trim().is_empty() {
+            // nothing to check until the puzzle's example is pasted into example.txt
+            return;
+        }
+        let field = parse(EXAMPLE).unwrap();
+        assert_eq!(field.height(), EXAMPLE.lines().count());
+    }
+}
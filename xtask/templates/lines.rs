@@ -0,0 +1,80 @@
+use std::path::PathBuf;
+
+use clap::Parser;
+
+use super::CommandImpl;
+use crate::error::{AocError, Location};
+
+use crate::utils::read_input;
+use nom::{character::complete::not_line_ending, IResult};
+
+#[derive(Parser, Debug)]
+pub struct Day0 {
+    #[clap(long, short)]
+    input: PathBuf,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Record {
+    pub text: String,
+}
+
+fn parse_record(input: &str) -> IResult<&str, Record> {
+    let (input, text) = not_line_ending(input)?;
+    Ok((input, Record { text: text.to_owned() }))
+}
+
+// one record per line, which parse_record has to consume entirely
+pub fn parse(input: &str) -> Result<Vec<Record>, AocError> {
+    let mut records: Vec<Record> = Vec::new();
+    for (i, line) in input.lines().enumerate() {
+        match parse_record(line) {
+            Ok(("", record)) => records.push(record),
+            Ok((rest, _)) => {
+                let column = line[..line.len() - rest.len()].chars().count() + 1;
+                let location = Location { line: i + 1, column: Some(column) };
+                return Err(AocError::parse_at(location, format!("unexpected {rest:?}")));
+            }
+            Err(e) => return Err(AocError::parse_at(Location::line(i + 1), e.to_string())),
+        }
+    }
+    Ok(records)
+}
+
+pub fn part1(input: &str) -> Result<usize, AocError> {
+    let records = parse(input)?;
+    todo!("solve part 1 for {} records", records.len())
+}
+
+pub fn part2(input: &str) -> Result<usize, AocError> {
+    let records = parse(input)?;
+    todo!("solve part 2 for {} records", records.len())
+}
+
+impl CommandImpl for Day0 {
+    fn main(&self) -> Result<(), AocError> {
+        let string = read_input(&self.input)?;
+        println!("part 1: {}", part1(&string)?);
+        println!("part 2: {}", part2(&string)?);
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../../../examples/day0/example.txt");
+
+    // only the shape for now, check the parsed values once the puzzle is understood
+    #[test]
+    fn test_parse() {
+        if EXAMPLE.trim().is_empty() {
+            // nothing to check until the puzzle's example is pasted into example.txt
+            return;
+        }
+        let records = parse(EXAMPLE).unwrap();
+        assert_eq!(records.len(), EXAMPLE.lines().count());
+    }
+}
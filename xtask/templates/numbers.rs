@@ -0,0 +1,60 @@
+use std::path::PathBuf;
+
+use clap::Parser;
+
+use super::CommandImpl;
+use crate::error::AocError;
+
+use crate::utils::{
+    parse::{complete, lines, numbers},
+    read_input,
+};
+
+#[derive(Parser, Debug)]
+pub struct Day0 {
+    #[clap(long, short)]
+    input: PathBuf,
+}
+
+// whitespace separated numbers, one row per line
+pub fn parse(input: &str) -> Result<Vec<Vec<i64>>, AocError> {
+    complete(input, lines(numbers))
+}
+
+pub fn part1(input: &str) -> Result<usize, AocError> {
+    let rows = parse(input)?;
+    todo!("solve part 1 for {} rows", rows.len())
+}
+
+pub fn part2(input: &str) -> Result<usize, AocError> {
+    let rows = parse(input)?;
+    todo!("solve part 2 for {} rows", rows.len())
+}
+
+impl CommandImpl for Day0 {
+    fn main(&self) -> Result<(), AocError> {
+        let string = read_input(&self.input)?;
+        println!("part 1: {}", part1(&string)?);
+        println!("part 2: {}", part2(&string)?);
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../../../examples/day0/example.txt");
+
+    // only the shape for now, check the parsed values once the puzzle is understood
+    #[test]
+    fn test_parse() {
+        if EXAMPLE.trim().is_empty() {
+            // nothing to check until the puzzle's example is pasted into example.txt
+            return;
+        }
+        let rows = parse(EXAMPLE).unwrap();
+        assert_eq!(rows.len(), EXAMPLE.lines().count());
+    }
+}
@@ -0,0 +1,76 @@
+use std::path::PathBuf;
+
+use clap::Parser;
+
+use super::CommandImpl;
+use crate::error::AocError;
+
+use crate::utils::{
+    parse::{complete, labelled, lines, numbers},
+    read_input,
+};
+use nom::{
+    bytes::complete::tag, character::complete::space0, multi::separated_list1,
+    sequence::pair, IResult,
+};
+
+#[derive(Parser, Debug)]
+pub struct Day0 {
+    #[clap(long, short)]
+    input: PathBuf,
+}
+
+// `Card 1: 41 48 83 | 83 86 6`, groups of numbers separated by `|`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Record {
+    pub id: u32,
+    pub groups: Vec<Vec<u32>>,
+}
+
+fn parse_record(input: &str) -> IResult<&str, Record> {
+    let (input, (id, groups)) =
+        labelled("Card", separated_list1(pair(space0, tag("|")), numbers))(input)?;
+    Ok((input, Record { id, groups }))
+}
+
+pub fn parse(input: &str) -> Result<Vec<Record>, AocError> {
+    complete(input, lines(parse_record))
+}
+
+pub fn part1(input: &str) -> Result<usize, AocError> {
+    let records = parse(input)?;
+    todo!("solve part 1 for {} records", records.len())
+}
+
+pub fn part2(input: &str) -> Result<usize, AocError> {
+    let records = parse(input)?;
+    todo!("solve part 2 for {} records", records.len())
+}
+
+impl CommandImpl for Day0 {
+    fn main(&self) -> Result<(), AocError> {
+        let string = read_input(&self.input)?;
+        println!("part 1: {}", part1(&string)?);
+        println!("part 2: {}", part2(&string)?);
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../../../examples/day0/example.txt");
+
+    // only the shape for now, check the parsed values once the puzzle is understood
+    #[test]
+    fn test_parse() {
+        if EXAMPLE.trim().is_empty() {
+            // nothing to check until the puzzle's example is pasted into example.txt
+            return;
+        }
+        let records = parse(EXAMPLE).unwrap();
+        assert_eq!(records.len(), EXAMPLE.lines().count());
+    }
+}
@@ -0,0 +1,77 @@
+use std::path::PathBuf;
+
+use clap::Parser;
+
+use super::CommandImpl;
+use crate::error::AocError;
+
+use crate::utils::{read_input, sections};
+use nom::{
+    character::complete::{space1, u64},
+    multi::separated_list1,
+    IResult,
+};
+
+#[derive(Parser, Debug)]
+pub struct Day0 {
+    #[clap(long, short)]
+    input: PathBuf,
+}
+
+// `name:` followed by rows of numbers, like `seeds: 79 14` or `seed-to-soil map:`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Section {
+    pub name: Option<String>,
+    pub rows: Vec<Vec<u64>>,
+}
+
+fn parse_row(input: &str) -> IResult<&str, Vec<u64>> {
+    separated_list1(space1, u64)(input)
+}
+
+pub fn parse(input: &str) -> Result<Vec<Section>, AocError> {
+    let mut parsed = vec![];
+    for section in sections(input) {
+        let rows = section.parse(parse_row)?;
+        parsed.push(Section { name: section.header.map(str::to_owned), rows });
+    }
+    Ok(parsed)
+}
+
+pub fn part1(input: &str) -> Result<usize, AocError> {
+    let sections = parse(input)?;
+    todo!("solve part 1 for {} sections", sections.len())
+}
+
+pub fn part2(input: &str) -> Result<usize, AocError> {
+    let sections = parse(input)?;
+    todo!("solve part 2 for {} sections", sections.len())
+}
+
+impl CommandImpl for Day0 {
+    fn main(&self) -> Result<(), AocError> {
+        let string = read_input(&self.input)?;
+        println!("part 1: {}", part1(&string)?);
+        println!("part 2: {}", part2(&string)?);
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../../../examples/day0/example.txt");
+
+    // only the shape for now, check the parsed values once the puzzle is understood
+    #[test]
+    fn test_parse() {
+        if EXAMPLE.trim().is_empty() {
+            // nothing to check until the puzzle's example is pasted into example.txt
+            return;
+        }
+        let sections = parse(EXAMPLE).unwrap();
+        assert_eq!(sections.len(), EXAMPLE.trim_end().split("\n\n").count());
+    }
+}